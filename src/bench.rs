@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use crate::solver::{Solver, Strategy, states_pattern};
+use crate::{LetterState, score_guess};
+
+// A game that still isn't solved after this many guesses is given up on.
+const GIVE_UP_AFTER: usize = 20;
+
+// How a strategy did against every word in an answer pool, as written out by `rustle bench`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct BenchReport {
+    pub rustle_version: String,
+    pub strategy: String,
+    pub word_length: usize,
+    pub max_tries: u8,
+    pub hard_mode: bool,
+    pub threads: usize,
+    pub games: usize,
+    pub failures: usize, // Games that weren't solved within max_tries.
+    pub average_guesses: f64, // Over the games that were solved at all.
+    pub worst_case: usize,
+    pub distribution: Vec<u32>, // Index 0 counts games solved in one guess, index 1 in two, etc.
+    pub unsolved: Vec<String>, // Answers given up on after 20 guesses.
+    pub seconds: f64
+}
+
+// Plays the strategy against every answer in the pool. Games that reach the same point make the
+// same choices, so rather than playing each answer separately the answers are split up by the
+// feedback they give, building the strategy's whole decision tree once. The subtrees under the
+// first guess are shared out between threads.
+pub fn run_bench(guesses: &[String], answers: &[String], strategy: &dyn Strategy, max_tries: u8, hard_mode: bool, threads: usize) -> BenchReport {
+    let started = Instant::now();
+    let threads = threads.max(1);
+    let root = Solver::with_words(guesses, answers, hard_mode).with_threads(threads);
+
+    let mut results: Vec<(String, Option<usize>)> = Vec::new();
+    let mut subtrees: Vec<Solver> = Vec::new();
+
+    if let Some(guess) = next_guess(&root, strategy) {
+        for (states, _) in partition(&guess, root.candidates()) {
+            if states.iter().all(|status| *status == LetterState::Correct) {
+                results.push((guess.clone(), Some(1)));
+            } else {
+                let mut child = root.clone().with_threads(1);
+                child.apply(&guess, &states);
+                subtrees.push(child);
+            }
+        }
+    }
+
+    // Biggest subtrees first so no thread is left with a large one at the end.
+    subtrees.sort_by_key(|subtree| std::cmp::Reverse(subtree.candidates().len()));
+    let queue = Mutex::new(subtrees.into_iter());
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads).map(|_| scope.spawn(|| {
+            let mut worker_results = Vec::new();
+
+            loop {
+                let Some(subtree) = queue.lock().expect("Bench queue poisoned").next() else { break };
+                play(subtree, strategy, &mut worker_results);
+            }
+
+            worker_results
+        })).collect();
+
+        for worker in workers {
+            results.extend(worker.join().expect("Bench thread panicked"));
+        }
+    });
+
+    summarize(&results, strategy, answers, max_tries, hard_mode, threads, started)
+}
+
+// Plays every answer still possible from this point, recording how many guesses each one took.
+fn play(solver: Solver, strategy: &dyn Strategy, results: &mut Vec<(String, Option<usize>)>) {
+    let turn = solver.played() + 1;
+
+    let Some(guess) = next_guess(&solver, strategy) else {
+        results.extend(solver.candidates().iter().map(|word| (word.to_string(), None)));
+        return
+    };
+
+    for (states, bucket) in partition(&guess, solver.candidates()) {
+        if states.iter().all(|status| *status == LetterState::Correct) {
+            results.push((guess.clone(), Some(turn)));
+        } else if turn >= GIVE_UP_AFTER {
+            results.extend(bucket.iter().map(|word| (word.to_string(), None)));
+        } else {
+            let mut child = solver.clone();
+            child.apply(&guess, &states);
+            play(child, strategy, results);
+        }
+    }
+}
+
+fn next_guess(solver: &Solver, strategy: &dyn Strategy) -> Option<String> {
+    if let Some(word) = solver.obvious_guess() {
+        return Some(word.to_string())
+    }
+
+    strategy.choose(solver).or_else(|| solver.candidates().first().map(|word| word.to_string()))
+}
+
+// Groups the candidates by the feedback the guess would get against them.
+fn partition<'a>(guess: &str, candidates: &[&'a str]) -> Vec<(Vec<LetterState>, Vec<&'a str>)> {
+    let mut buckets: BTreeMap<usize, (Vec<LetterState>, Vec<&'a str>)> = BTreeMap::new();
+
+    for candidate in candidates {
+        let states = score_guess(guess, candidate);
+        buckets.entry(states_pattern(&states)).or_insert_with(|| (states, Vec::new())).1.push(candidate);
+    }
+
+    buckets.into_values().collect()
+}
+
+fn summarize(results: &[(String, Option<usize>)], strategy: &dyn Strategy, answers: &[String], max_tries: u8, hard_mode: bool, threads: usize, started: Instant) -> BenchReport {
+    let solved: Vec<usize> = results.iter().filter_map(|(_, guesses)| *guesses).collect();
+    let worst_case = solved.iter().copied().max().unwrap_or(0);
+
+    let mut distribution = vec![0; worst_case.max(usize::from(max_tries))];
+    for guesses in &solved {
+        distribution[guesses - 1] += 1;
+    }
+
+    let mut unsolved: Vec<String> = results.iter().filter(|(_, guesses)| guesses.is_none()).map(|(word, _)| word.clone()).collect();
+    unsolved.sort();
+
+    BenchReport {
+        rustle_version: String::from(env!("CARGO_PKG_VERSION")),
+        strategy: strategy.name(),
+        word_length: answers.first().map_or(0, |word| word.chars().count()),
+        max_tries,
+        hard_mode,
+        threads,
+        games: results.len(),
+        failures: results.len() - solved.iter().filter(|guesses| **guesses <= usize::from(max_tries)).count(),
+        average_guesses: if solved.is_empty() { 0.0 } else { solved.iter().sum::<usize>() as f64 / solved.len() as f64 },
+        worst_case,
+        distribution,
+        unsolved,
+        seconds: started.elapsed().as_secs_f64()
+    }
+}
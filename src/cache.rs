@@ -0,0 +1,51 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+
+use crate::error::RustleError;
+use crate::storage;
+use crate::words::SolutionResponse;
+
+// Keeps every fetched daily solution on disk, one JSON file per date, so a daily puzzle can still be
+// played when the network drops. With `refresh` set, cached entries are ignored but still rewritten.
+pub struct SolutionCache {
+    dir: PathBuf,
+    refresh: bool
+}
+
+impl SolutionCache {
+    pub fn new(dir: PathBuf, refresh: bool) -> SolutionCache {
+        SolutionCache { dir, refresh }
+    }
+
+    // Solutions are cached under the XDG cache directory (e.g. ~/.cache/rustle/solutions).
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("rustle").join("solutions"))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn entry_path(&self, date: NaiveDate) -> PathBuf {
+        self.dir.join(format!("{}.json", date.format("%Y-%m-%d")))
+    }
+
+    // A missing, unreadable or corrupt entry is treated as a cache miss.
+    pub fn get(&self, date: NaiveDate) -> Option<SolutionResponse> {
+        if self.refresh {
+            return None
+        }
+
+        let raw_entry = fs::read_to_string(self.entry_path(date)).ok()?;
+
+        serde_json::from_str(&raw_entry).ok()
+    }
+
+    pub fn store(&self, date: NaiveDate, response: &SolutionResponse) -> Result<(), RustleError> {
+        let path = self.entry_path(date);
+
+        storage::write_json(&path, response, |err| RustleError::Storage(format!("Failed to cache the solution in \"{}\": {}", path.display(), err)))
+    }
+}
@@ -0,0 +1,366 @@
+use std::collections::HashSet;
+use std::ffi::{OsStr, OsString};
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use clap::builder::BoolishValueParser;
+use clap::{ArgAction, Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+
+use crate::config::Config;
+use crate::error::RustleError;
+use crate::lang::Language;
+use crate::share::ShareFormat;
+use crate::solver::FeedbackRow;
+use crate::theme::Theme;
+use crate::words::{self, DEFAULT_WORD_LENGTH, MAX_WORD_LENGTH, MIN_WORD_LENGTH};
+use crate::{DEFAULT_TRIES, MAX_ALLOWED_TRIES};
+
+// The command line, parsed into the command to run and its typed options. Every option can also be
+// set with a RUSTLE_ environment variable named after it (e.g. RUSTLE_TRIES=8 for --tries 8, or
+// RUSTLE_HARD=1 for --hard), and options given on the command line win over the environment, which
+// wins over the config file.
+#[derive(Parser, Debug, Clone, PartialEq, Eq)]
+#[command(
+    name = "rustle",
+    version,
+    about = "An open source Wordle CLI built in Rust",
+    after_help = "Without a command, Rustle plays a game: see `rustle play --help` for its options."
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+
+    // Read before parsing, see config_arg.
+    #[arg(long, global = true, env = "RUSTLE_CONFIG", value_name = "PATH", help = "Read settings from this config file instead of the default one")]
+    pub config: Option<PathBuf>,
+
+    #[arg(long, global = true, env = "RUSTLE_COLOR", value_name = "WHEN", value_enum, default_value_t = ColorChoice::Auto, help = "Whether to draw in color: auto (when writing to a terminal and NO_COLOR isn't set), always or never")]
+    pub color: ColorChoice
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never
+}
+
+impl ColorChoice {
+    // Whether to draw the full colored board. Otherwise Rustle prints plain text, e.g. into a file
+    // or a CI log. NO_COLOR only counts when it's set to something, see https://no-color.org.
+    pub fn use_color(&self, stdout_is_terminal: bool, no_color: Option<&OsStr>) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => stdout_is_terminal && no_color.is_none_or(|no_color| no_color.is_empty())
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    #[command(about = "Play a game, the default when no command is given")]
+    Play(PlayArgs),
+    #[command(about = "Show your statistics")]
+    Stats(StatsArgs),
+    #[command(about = "Suggest the next guess for a game played elsewhere, given its guesses and colors")]
+    Solve(SolveArgs),
+    #[command(about = "Print the share text for a finished daily puzzle")]
+    Share(ShareArgs),
+    #[command(about = "List the words that can be guessed")]
+    Words(WordsArgs),
+    #[command(about = "Play a solver strategy against every answer and print the results as JSON")]
+    Bench(BenchArgs),
+    #[command(about = "Cache the daily puzzles for the next few days, to play them offline")]
+    Prefetch(PrefetchArgs)
+}
+
+// Which puzzle to play: today's daily one by default, or a past or future daily puzzle, or a random one.
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleArgs {
+    #[arg(long, env = "RUSTLE_OFFLINE", value_parser = BoolishValueParser::new(), action = ArgAction::Set, num_args = 0..=1, require_equals = true, default_value = "false", default_missing_value = "true", conflicts_with_all = ["date", "puzzle", "yesterday"], help = "Play a random puzzle instead of the NYT's daily one")]
+    pub offline: bool,
+
+    #[arg(long, env = "RUSTLE_SEED", conflicts_with_all = ["date", "puzzle", "yesterday"], help = "Play the random puzzle picked by a seed, e.g. 4821")]
+    pub seed: Option<u64>,
+
+    #[arg(long, env = "RUSTLE_DATE", value_name = "YYYY-MM-DD", value_parser = words::parse_puzzle_date, conflicts_with_all = ["puzzle", "yesterday"], help = "Play the daily puzzle for a date")]
+    pub date: Option<NaiveDate>,
+
+    #[arg(long, env = "RUSTLE_PUZZLE", value_name = "NUMBER", value_parser = parse_puzzle_number, allow_negative_numbers = true, conflicts_with = "yesterday", help = "Play a daily puzzle by its number")]
+    pub puzzle: Option<NaiveDate>,
+
+    #[arg(long, env = "RUSTLE_YESTERDAY", value_parser = BoolishValueParser::new(), help = "Play yesterday's daily puzzle")]
+    pub yesterday: bool,
+
+    #[arg(long, env = "RUSTLE_REFRESH", value_parser = BoolishValueParser::new(), help = "Fetch the daily puzzle again rather than using the cached one")]
+    pub refresh: bool
+}
+
+impl PuzzleArgs {
+    // The daily puzzle that was asked for with --date, --puzzle or --yesterday, if any.
+    pub fn requested_date(&self, today: NaiveDate) -> Option<NaiveDate> {
+        self.date.or(self.puzzle).or_else(|| if self.yesterday { today.pred_opt() } else { None })
+    }
+}
+
+// Which words to play with.
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct WordArgs {
+    #[arg(long, env = "RUSTLE_LENGTH", default_value_t = DEFAULT_WORD_LENGTH, value_parser = parse_word_length, help = "How many letters the words have")]
+    pub length: usize,
+
+    #[arg(long, env = "RUSTLE_LANG", default_value = "en", help = "The word pack to play with: en, es, de or fr")]
+    pub lang: Language,
+
+    #[arg(long, env = "RUSTLE_WORDLIST", value_name = "PATH", help = "Load the words that can be guessed from a file")]
+    pub wordlist: Option<PathBuf>,
+
+    #[arg(long, env = "RUSTLE_ANSWERS", value_name = "PATH", help = "Load the words that can be the answer from a file")]
+    pub answers: Option<PathBuf>
+}
+
+impl WordArgs {
+    // Loads the (guess list, answer list) given with --wordlist and --answers, if either was. A guess
+    // list on its own is used for the answers too, while answers on their own are played alongside
+    // the bundled guesses, if there are any for the word length. Answers can always be guessed, so any
+    // missing from the guess list are added to it for the solver.
+    pub fn custom_lists(&self) -> Result<Option<WordLists>, RustleError> {
+        let load = |path: &PathBuf| words::load_wordlist_file(path, self.lang, self.length);
+
+        let (mut wordlist, answerlist) = match (self.wordlist.as_ref().map(load).transpose()?, self.answers.as_ref().map(load).transpose()?) {
+            (Some(wordlist), Some(answerlist)) => (wordlist, answerlist),
+            (Some(wordlist), None) => (wordlist.clone(), wordlist),
+            (None, Some(answerlist)) => (words::load_bundled_words(self.lang, self.length).map_or_else(|_| Vec::new(), |(wordlist, _)| wordlist), answerlist),
+            (None, None) => return Ok(None)
+        };
+
+        let known: HashSet<String> = wordlist.iter().cloned().collect();
+        wordlist.extend(answerlist.iter().filter(|answer| !known.contains(*answer)).cloned());
+
+        Ok(Some((wordlist, answerlist)))
+    }
+
+    // The custom lists if any were given, otherwise the bundled ones.
+    pub fn lists(&self) -> Result<WordLists, RustleError> {
+        match self.custom_lists()? {
+            Some(lists) => Ok(lists),
+            None => words::load_bundled_words(self.lang, self.length)
+        }
+    }
+}
+
+// A (guess list, answer list) pair.
+pub type WordLists = (Vec<String>, Vec<String>);
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct PlayArgs {
+    #[command(flatten)]
+    pub puzzle: PuzzleArgs,
+
+    #[command(flatten)]
+    pub words: WordArgs,
+
+    #[arg(long, env = "RUSTLE_TRIES", default_value_t = DEFAULT_TRIES, value_parser = clap::value_parser!(u8).range(1..=i64::from(MAX_ALLOWED_TRIES)), help = "How many guesses you get")]
+    pub tries: u8,
+
+    #[arg(long, env = "RUSTLE_HARD", value_parser = BoolishValueParser::new(), action = ArgAction::Set, num_args = 0..=1, require_equals = true, default_value = "false", default_missing_value = "true", help = "Revealed hints must be used in later guesses")]
+    pub hard: bool,
+
+    #[arg(long, env = "RUSTLE_NEW", value_parser = BoolishValueParser::new(), help = "Start over rather than resuming the saved game")]
+    pub new: bool,
+
+    #[arg(long, env = "RUSTLE_HINT", value_parser = BoolishValueParser::new(), help = "Print a hint for the next guess and exit")]
+    pub hint: bool,
+
+    #[arg(long, env = "RUSTLE_ANALYZE", value_parser = BoolishValueParser::new(), help = "Review every guess once the game is over")]
+    pub analyze: bool,
+
+    #[arg(long, env = "RUSTLE_SHARE_FORMAT", value_name = "FORMAT", default_value = "emoji", help = "The share grid style: emoji, contrast or ascii")]
+    pub share_format: ShareFormat,
+
+    #[arg(long, env = "RUSTLE_THEME", default_value = "default", help = "The tile colors: default, contrast (orange and blue), light or mono")]
+    pub theme: Theme,
+
+    // Older spelling of `rustle stats`.
+    #[arg(long, hide = true)]
+    pub stats: bool
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct StatsArgs {
+    #[arg(long, env = "RUSTLE_THEME", default_value = "default", help = "The bar colors: default, contrast (orange and blue), light or mono")]
+    pub theme: Theme,
+
+    #[arg(long, env = "RUSTLE_LANG", default_value = "en", help = "The language to show the statistics in: en, es, de or fr")]
+    pub lang: Language
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct SolveArgs {
+    #[command(flatten)]
+    pub words: WordArgs,
+
+    #[arg(long, env = "RUSTLE_HARD", value_parser = BoolishValueParser::new(), action = ArgAction::Set, num_args = 0..=1, require_equals = true, default_value = "false", default_missing_value = "true", help = "Only suggest guesses allowed in hard mode")]
+    pub hard: bool,
+
+    #[arg(long, env = "RUSTLE_TOP", default_value_t = 5, value_parser = clap::value_parser!(u64).range(1..), help = "How many guesses to suggest")]
+    pub top: u64,
+
+    #[arg(value_name = "GUESS:COLORS", help = "A guess and its colors so far, e.g. crane:.Y..G with G for green, Y for yellow and . for gray")]
+    pub rows: Vec<FeedbackRow>
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct ShareArgs {
+    #[command(flatten)]
+    pub puzzle: PuzzleArgs,
+
+    #[command(flatten)]
+    pub words: WordArgs,
+
+    #[arg(long, env = "RUSTLE_SHARE_FORMAT", value_name = "FORMAT", default_value = "emoji", help = "The share grid style: emoji, contrast or ascii")]
+    pub share_format: ShareFormat
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct WordsArgs {
+    #[command(flatten)]
+    pub words: WordArgs,
+
+    #[arg(long, env = "RUSTLE_SOLUTIONS", value_parser = BoolishValueParser::new(), help = "Only list the words that can be the answer")]
+    pub solutions: bool
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategyName {
+    Entropy,
+    Minimax
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub words: WordArgs,
+
+    #[arg(long, env = "RUSTLE_TRIES", default_value_t = DEFAULT_TRIES, value_parser = clap::value_parser!(u8).range(1..=i64::from(MAX_ALLOWED_TRIES)), help = "How many guesses the solver gets")]
+    pub tries: u8,
+
+    #[arg(long, env = "RUSTLE_HARD", value_parser = BoolishValueParser::new(), action = ArgAction::Set, num_args = 0..=1, require_equals = true, default_value = "false", default_missing_value = "true", help = "Play the solver in hard mode")]
+    pub hard: bool,
+
+    #[arg(long, env = "RUSTLE_STRATEGY", value_enum, default_value_t = StrategyName::Entropy, help = "Most expected information (entropy) or fewest answers left in the worst case (minimax)")]
+    pub strategy: StrategyName,
+
+    #[arg(long, env = "RUSTLE_OPENER", help = "Always open with this word")]
+    pub opener: Option<String>,
+
+    #[arg(long, env = "RUSTLE_THREADS", value_parser = clap::value_parser!(u64).range(1..), help = "How many threads to use, all cores by default")]
+    pub threads: Option<u64>
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct PrefetchArgs {
+    #[arg(value_name = "DAYS", value_parser = clap::value_parser!(u64).range(1..), help = "How many daily puzzles to cache, starting with today's")]
+    pub days: u64
+}
+
+// Parses the command line (including the program name) into the command to run. Anything that
+// doesn't start with a command is a game, e.g. `rustle --hard` is `rustle play --hard`. --help and
+// --version come back as errors too, see clap::Error::kind.
+pub fn parse_args<I, T>(args: I) -> Result<Command, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>
+    {
+    Ok(parse_args_with_config(args, &Config::default())?.command)
+}
+
+// Like parse_args, with the config file's settings as the defaults for any option that isn't given
+// on the command line or in the environment. Also returns the options shared by every command.
+pub fn parse_args_with_config<I, T>(args: I, config: &Config) -> Result<Cli, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>
+    {
+    let mut args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+
+    // Options shared by every command can come before it, e.g. `rustle --config work.toml stats`.
+    let mut first = 1;
+
+    while let Some(arg) = args.get(first).and_then(|arg| arg.to_str()) {
+        match arg {
+            "--config" | "--color" => first += 2,
+            _ if arg.starts_with("--config=") || arg.starts_with("--color=") => first += 1,
+            _ => break
+        }
+    }
+
+    let names_command = args.get(first).and_then(|arg| arg.to_str()).is_some_and(|arg| {
+        ["help", "-h", "--help", "-V", "--version"].contains(&arg) || Cli::command().find_subcommand(arg).is_some()
+    });
+
+    if !names_command && !args.is_empty() {
+        args.insert(1, OsString::from("play"));
+    }
+
+    let defaults = config.option_defaults();
+
+    let command = Cli::command().mut_subcommands(|subcommand| {
+        let is_play = subcommand.get_name() == "play";
+
+        subcommand.mut_args(|arg| {
+            // Online or offline is only a default for playing, `rustle share` should still find today's puzzle.
+            match defaults.iter().find(|(id, _)| arg.get_id() == *id && (is_play || *id != "offline")) {
+                Some((_, value)) => arg.default_value(value.clone()),
+                None => arg
+            }
+        })
+    });
+
+    Cli::from_arg_matches(&command.try_get_matches_from(args)?)
+}
+
+// The config file given with --config, found before the rest of the command line is parsed so its
+// settings can be used as defaults.
+pub fn config_arg(args: &[OsString]) -> Option<PathBuf> {
+    early_arg(args, "--config").map(PathBuf::from)
+}
+
+// The --color given on the command line, or else RUSTLE_COLOR, found before the rest of the command
+// line is parsed so errors in the config file (or the command line) are only colored when asked to be.
+pub fn early_color_choice(args: &[OsString], env_color: Option<&OsStr>) -> ColorChoice {
+    early_arg(args, "--color").or(env_color)
+        .and_then(|choice| ColorChoice::from_str(&choice.to_string_lossy(), true).ok())
+        .unwrap_or(ColorChoice::Auto)
+}
+
+// The value of a global option, given as `--name value` or `--name=value`.
+fn early_arg<'a>(args: &'a [OsString], name: &str) -> Option<&'a OsStr> {
+    let mut args = args.iter().skip(1).take_while(|arg| *arg != "--");
+
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next().map(OsString::as_os_str)
+        }
+
+        if let Some(value) = arg.to_str().and_then(|arg| arg.strip_prefix(name)).and_then(|rest| rest.strip_prefix('=')) {
+            return Some(OsStr::new(value))
+        }
+    }
+
+    None
+}
+
+fn parse_word_length(length: &str) -> Result<usize, String> {
+    match length.parse() {
+        Ok(length) if (MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(&length) => Ok(length),
+        _ => Err(format!("words must be between {} and {} letters long", MIN_WORD_LENGTH, MAX_WORD_LENGTH))
+    }
+}
+
+fn parse_puzzle_number(number: &str) -> Result<NaiveDate, RustleError> {
+    let number: i64 = number.parse().map_err(|_| RustleError::Parse(format!("Invalid puzzle number \"{}\".", number)))?;
+
+    words::puzzle_date(number)
+}
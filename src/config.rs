@@ -0,0 +1,179 @@
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use toml::Spanned;
+
+use crate::cache::SolutionCache;
+use crate::error::RustleError;
+use crate::lang::Language;
+use crate::save::SavedGame;
+use crate::share::ShareFormat;
+use crate::stats::Stats;
+use crate::theme::Theme;
+use crate::words::{MAX_WORD_LENGTH, MIN_WORD_LENGTH};
+use crate::MAX_ALLOWED_TRIES;
+
+// Settings read from config.toml, used wherever the command line and environment don't say
+// otherwise. Every key is optional, and unknown keys are an error rather than silently ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    pub offline: Option<bool>, // From `mode = "online"` or `mode = "offline"`.
+    pub hard: Option<bool>,
+    pub tries: Option<u8>,
+    pub length: Option<usize>,
+    pub lang: Option<Language>,
+    pub share_format: Option<ShareFormat>,
+    pub theme: Option<Theme>,
+    pub cache_dir: Option<PathBuf>,
+    pub stats_file: Option<PathBuf>,
+    pub games_dir: Option<PathBuf>
+}
+
+// The file as written, with the position of every value so mistakes can be pointed at.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+    mode: Option<Spanned<String>>,
+    hard: Option<bool>,
+    tries: Option<Spanned<i64>>,
+    length: Option<Spanned<i64>>,
+    lang: Option<Spanned<String>>,
+    share_format: Option<Spanned<String>>,
+    theme: Option<Spanned<String>>,
+    cache_dir: Option<String>,
+    stats_file: Option<String>,
+    games_dir: Option<String>
+}
+
+impl Config {
+    // The config lives in the XDG config directory (e.g. ~/.config/rustle/config.toml).
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("rustle").join("config.toml"))
+    }
+
+    // Loads the config file at `path`. A missing file is only an error when it was asked for
+    // explicitly (with --config), the default one is optional.
+    pub fn load(path: &Path, required: bool) -> Result<Config, RustleError> {
+        if !required && !path.exists() {
+            return Ok(Config::default())
+        }
+
+        let raw_config = fs::read_to_string(path)
+            .map_err(|err| RustleError::Storage(format!("Failed to read the config file \"{}\": {}", path.display(), err)))?;
+
+        Config::parse(&raw_config, path)
+    }
+
+    // Parses the contents of a config file. `path` is named in errors, and relative paths in the
+    // file are relative to the directory it's in.
+    pub fn parse(raw_config: &str, path: &Path) -> Result<Config, RustleError> {
+        let invalid = |span: Option<Range<usize>>, message: &str| {
+            let line = span.map_or(1, |span| raw_config[..span.start.min(raw_config.len())].matches('\n').count() + 1);
+            RustleError::Parse(format!("Invalid config file \"{}\", line {}: {}", path.display(), line, message))
+        };
+
+        let raw: RawConfig = toml::from_str(raw_config).map_err(|err| invalid(err.span(), err.message().trim_end()))?;
+
+        let offline = match raw.mode {
+            Some(mode) => match mode.get_ref().as_str() {
+                "online" => Some(false),
+                "offline" => Some(true),
+                other => return Err(invalid(Some(mode.span()), &format!("unknown mode \"{}\", expected \"online\" or \"offline\"", other)))
+            },
+            None => None
+        };
+
+        let tries = match raw.tries {
+            Some(tries) => Some(u8::try_from(*tries.get_ref()).ok().filter(|tries| (1..=MAX_ALLOWED_TRIES).contains(tries))
+                .ok_or_else(|| invalid(Some(tries.span()), &format!("tries must be between 1 and {}", MAX_ALLOWED_TRIES)))?),
+            None => None
+        };
+
+        let length = match raw.length {
+            Some(length) => Some(usize::try_from(*length.get_ref()).ok().filter(|length| (MIN_WORD_LENGTH..=MAX_WORD_LENGTH).contains(length))
+                .ok_or_else(|| invalid(Some(length.span()), &format!("words must be between {} and {} letters long", MIN_WORD_LENGTH, MAX_WORD_LENGTH)))?),
+            None => None
+        };
+
+        let lang = match raw.lang {
+            Some(lang) => Some(lang.get_ref().parse::<Language>().map_err(|err| invalid(Some(lang.span()), &err.to_string()))?),
+            None => None
+        };
+
+        let share_format = match raw.share_format {
+            Some(format) => Some(format.get_ref().parse::<ShareFormat>().map_err(|err| invalid(Some(format.span()), &err.to_string()))?),
+            None => None
+        };
+
+        let theme = match raw.theme {
+            Some(theme) => Some(theme.get_ref().parse::<Theme>().map_err(|err| invalid(Some(theme.span()), &err.to_string()))?),
+            None => None
+        };
+
+        let config_dir = path.parent().unwrap_or(Path::new(""));
+
+        Ok(Config {
+            offline,
+            hard: raw.hard,
+            tries,
+            length,
+            lang,
+            share_format,
+            theme,
+            cache_dir: raw.cache_dir.map(|dir| resolve_path(&dir, config_dir)),
+            stats_file: raw.stats_file.map(|file| resolve_path(&file, config_dir)),
+            games_dir: raw.games_dir.map(|dir| resolve_path(&dir, config_dir))
+        })
+    }
+
+    // The command line options the config sets, as (option id, value) pairs to use as their defaults.
+    pub fn option_defaults(&self) -> Vec<(&'static str, String)> {
+        let mut defaults = Vec::new();
+
+        if let Some(offline) = self.offline {
+            defaults.push(("offline", offline.to_string()));
+        }
+        if let Some(hard) = self.hard {
+            defaults.push(("hard", hard.to_string()));
+        }
+        if let Some(tries) = self.tries {
+            defaults.push(("tries", tries.to_string()));
+        }
+        if let Some(length) = self.length {
+            defaults.push(("length", length.to_string()));
+        }
+        if let Some(lang) = self.lang {
+            defaults.push(("lang", lang.code().to_string()));
+        }
+        if let Some(share_format) = self.share_format {
+            defaults.push(("share_format", share_format.name().to_string()));
+        }
+        if let Some(theme) = self.theme {
+            defaults.push(("theme", theme.name().to_string()));
+        }
+
+        defaults
+    }
+
+    pub fn cache_dir(&self) -> Option<PathBuf> {
+        self.cache_dir.clone().or_else(SolutionCache::default_dir)
+    }
+
+    pub fn stats_path(&self) -> Option<PathBuf> {
+        self.stats_file.clone().or_else(Stats::default_path)
+    }
+
+    pub fn games_dir(&self) -> Option<PathBuf> {
+        self.games_dir.clone().or_else(SavedGame::default_dir)
+    }
+}
+
+// Expands a leading ~ to the home directory, and makes relative paths relative to the config file.
+fn resolve_path(path: &str, config_dir: &Path) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => config_dir.join(path)
+    }
+}
@@ -1,8 +1,17 @@
 use std::io;
-use std::io::Write;
+use std::io::{IsTerminal, Write};
 use std::boxed::Box;
 use crossterm::{QueueableCommand, ExecutableCommand, cursor, terminal};
-use crate::Letter;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crate::{Letter, LetterState};
+use crate::error::RustleError;
+use crate::game::Game;
+use crate::keyboard;
+use crate::lang::{self, Language};
+use crate::render::{GameEnd, Key, Renderer};
+use crate::solver::RowAnalysis;
+use crate::stats::Stats;
+use crate::theme::{ColorDepth, Theme};
 
 pub enum TermFormatter {
     GreenBg,
@@ -31,24 +40,58 @@ impl TermFormatter {
             TermFormatter::BlackFg => String::from("\x1b[0;30m"),
             TermFormatter::RedFg => String::from("\x1b[0;31m"),
             TermFormatter::DefaultBold => String::from("\x1b[1m"),
-            TermFormatter::BlackBold => TermFormatter::get_bold(&TermFormatter::BlackFg).to_owned(),
-            TermFormatter::GreenBold => TermFormatter::get_bold(&TermFormatter::GreenFg).to_owned(),
-            TermFormatter::RedBold => TermFormatter::get_bold(&TermFormatter::RedFg).to_owned(),
+            TermFormatter::BlackBold => get_bold(&TermFormatter::BlackFg.as_str()),
+            TermFormatter::GreenBold => get_bold(&TermFormatter::GreenFg.as_str()),
+            TermFormatter::RedBold => get_bold(&TermFormatter::RedFg.as_str()),
             TermFormatter::Clear => String::from("\x1b[0m"),
             TermFormatter::SlowBlink => String::from("\x1b[5m")
         }
     }
-    // This is neccesary as it appears Windows CLIs don't like color & bold stacked.
-    fn get_bold<'a>(color: &'a TermFormatter) -> String {
-        let os = std::env::consts::OS;
-        if os == "linux" || os == "macos" {
-            format!("{}{}", color.as_str(), TermFormatter::DefaultBold.as_str())
-        } else {
-            color.as_str()
-        }
+
+    // The escape code, or nothing for output that isn't drawn in color.
+    pub fn as_str_if(&self, color: bool) -> String {
+        if color { self.as_str() } else { String::new() }
+    }
+}
+
+// This is neccesary as it appears Windows CLIs don't like color & bold stacked.
+pub(crate) fn get_bold(color: &str) -> String {
+    let os = std::env::consts::OS;
+    if os == "linux" || os == "macos" {
+        format!("{}{}", color, TermFormatter::DefaultBold.as_str())
+    } else {
+        color.to_string()
+    }
+}
+
+// Keeps the terminal in raw mode for as long as it's alive. Dropping it, including while unwinding
+// from a panic, always hands the terminal back in its normal state.
+pub struct RawModeGuard {}
+
+impl RawModeGuard {
+    pub fn enable() -> Result<RawModeGuard, RustleError> {
+        terminal::enable_raw_mode()?;
+
+        Ok(RawModeGuard {})
+    }
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
     }
 }
 
+// Makes sure a panic message is never printed while the terminal is still in raw mode.
+pub fn restore_terminal_on_panic() {
+    let default_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = terminal::disable_raw_mode();
+        default_hook(info);
+    }));
+}
+
 pub struct Logo {}
 
 impl Logo {
@@ -93,37 +136,61 @@ pub struct RustleDisplay {
     overall_height: u8,
     overall_width: u8,
     game_height: u8, // The height of the main game canvas, not including the logo.
-    offline: bool
+    board_width: u8, // The width of one row of tiles.
+    language: Language, // Picks the keyboard layout drawn under the board and the end screen's language.
+    theme: Theme,
+    depth: ColorDepth,
+    offline: bool,
+    raw_mode: Option<RawModeGuard> // Held while a guess is typed into the tiles.
 }
 
 impl RustleDisplay {
-    pub fn initialize_ui(offline: bool) -> Result<RustleDisplay, Box<dyn std::error::Error>> {
-        // Initialize a "canvas" that accounts for the size of the logo, input fields, and actual game UI.
+    // A display with no board yet, for messages printed before the game starts and for the screens
+    // shown outside of a game, like `rustle stats`. Renderer::begin_game makes room for the board.
+    pub fn new(language: Language, theme: Theme) -> RustleDisplay {
+        RustleDisplay {
+            stdout: io::stdout(),
+            overall_height: 0,
+            overall_width: 0,
+            game_height: 0,
+            board_width: 0,
+            language,
+            theme,
+            depth: ColorDepth::detect(),
+            offline: false,
+            raw_mode: None
+        }
+    }
 
-        let mut stdout: io::Stdout = io::stdout();
+    // Initializes a "canvas" that accounts for the size of the logo, input fields, and actual game UI.
+    fn initialize_ui(&mut self, offline: bool, word_length: usize, max_tries: u8) -> Result<(), RustleError> {
+        const LOGO_WIDTH: u8 = 58;
+        const TILE_WIDTH: usize = 4; // A letter padded by a space either side, then a gap.
 
-        stdout.queue(cursor::SavePosition)?;
+        // The guess rows with a space after each, the keyboard rows with a blank line beneath them,
+        // with two lines for prompts & inputs.
+        let too_big = |_| RustleError::Terminal(String::from("The board is too big to draw."));
 
-        const GAME_HEIGHT: u8 = 6 * 2 + 2;  // The size of the guess_list array with a space after each item, with two lines for prompts & inputs.
+        let keyboard = self.language.keyboard();
+        let game_height = u8::try_from(usize::from(max_tries) * 2 + keyboard.len() + 1 + 2).map_err(too_big)?;
+        let board_width = u8::try_from(word_length * TILE_WIDTH).map_err(too_big)?;
 
-        let overall_height = usize::from(GAME_HEIGHT) + Logo::get_logo(offline).lines().count();
+        let overall_height = usize::from(game_height) + Logo::get_logo(offline).lines().count();
 
         for _ in 1..=(overall_height) {
-            stdout.write_all(format!("\n").as_bytes())?;
+            self.stdout.write_all("\n".as_bytes())?;
         }
 
-        let overall_height = u8::try_from(overall_height)?;
-    
-        Ok(RustleDisplay { 
-            stdout: stdout,
-            overall_height: overall_height,
-            overall_width: 58,
-            game_height: 6 * 2 + 2,
-            offline: offline
-        })
+        self.overall_height = u8::try_from(overall_height).map_err(too_big)?;
+        self.overall_width = LOGO_WIDTH.max(board_width);
+        self.game_height = game_height;
+        self.board_width = board_width;
+        self.offline = offline;
+
+        Ok(())
     }
 
-    pub fn draw_logo(&mut self) -> io::Result<()> {
+    fn draw_logo(&mut self) -> Result<(), RustleError> {
         self.stdout.queue(cursor::MoveUp(u16::from(self.overall_height)))?;
         self.stdout.write_all(format!("{}{}{}", TermFormatter::DefaultBold.as_str(), Logo::get_logo(self.offline), TermFormatter::Clear.as_str()).as_bytes())?;
         self.stdout.queue(cursor::MoveDown(u16::from(self.game_height)))?;
@@ -132,28 +199,93 @@ impl RustleDisplay {
         Ok(())
     }
 
-    pub fn draw_ui(&mut self, guess_list: &[Vec<Letter>; 6]) -> io::Result<()> {
+    // Redraws the guess rows in place, with the letters typed so far in the `pending` row if there is one.
+    fn draw_rows(&mut self, guess_list: &[Vec<Letter>], pending: Option<(usize, &str)>) -> io::Result<()> {
         self.stdout.queue(cursor::MoveUp(u16::from(self.game_height)))?;
         self.stdout.queue(terminal::Clear(terminal::ClearType::FromCursorDown))?;
-    
-        for guess in guess_list {
-            for _ in 1..=((self.overall_width - 20)/2) {
+
+        for (index, guess) in guess_list.iter().enumerate() {
+            for _ in 1..=((self.overall_width - self.board_width)/2) {
                 self.stdout.write_all(" ".as_bytes())?;
             }
-            for letter in guess {
-                self.stdout.write_all(format!("{}{} {} {} ", TermFormatter::BlackBold.as_str(), letter.get_ansi_color(), letter.value, TermFormatter::Clear.as_str()).as_bytes())?;
+
+            match pending {
+                Some((row, typed)) if row == index => {
+                    let mut typed_chars = typed.chars();
+                    for _ in guess {
+                        self.draw_tile(&Letter::new(typed_chars.next().unwrap_or(' '), LetterState::NotExists))?;
+                    }
+                },
+                _ => {
+                    for letter in guess {
+                        self.draw_tile(letter)?;
+                    }
+                }
             }
-            self.stdout.write_all("\n\n".as_bytes())?;
+
+            self.stdout.write_all("\r\n\r\n".as_bytes())?;
         }
+
+        Ok(())
+    }
+
+    fn draw_tile(&mut self, letter: &Letter) -> io::Result<()> {
+        self.stdout.write_all(format!("{} ", self.theme.tile(letter.value, &letter.status, self.depth)).as_bytes())?;
+
+        Ok(())
+    }
+
+    // Draws the keyboard under the guess rows.
+    fn draw_keys(&mut self, guess_list: &[Vec<Letter>]) -> io::Result<()> {
+        for row in keyboard::keyboard_rows(guess_list, self.language.keyboard()) {
+            let row_width = u8::try_from(row.len() * 4).unwrap_or(self.overall_width);
+            for _ in 1..=(self.overall_width.saturating_sub(row_width)/2) {
+                self.stdout.write_all(" ".as_bytes())?;
+            }
+            for key in row {
+                self.stdout.write_all(format!("{} ", self.theme.tile(lang::to_uppercase(key.value()), key.status(), self.depth)).as_bytes())?;
+            }
+            self.stdout.write_all("\r\n".as_bytes())?;
+        }
+        self.stdout.write_all("\r\n".as_bytes())?;
         self.stdout.flush()?;
-    
+
+        Ok(())
+    }
+
+    pub fn terminate_ui(&mut self) -> Result<(), RustleError> {
+        // Leave the cursor below the canvas so anything printed afterwards doesn't draw over the game.
+        self.stdout.queue(cursor::MoveUp(u16::from(self.overall_height)))?;
+        for _ in 1..=(self.overall_height) {
+            self.stdout.write_all("\n".as_bytes())?;
+        }
         self.stdout.execute(cursor::Show)?;
 
         Ok(())
- 
     }
+}
 
-    pub fn draw_input_error(&mut self, error_msg: &str) -> io::Result<()> {
+impl Renderer for RustleDisplay {
+    fn begin_game(&mut self, game: &Game) -> Result<(), RustleError> {
+        self.initialize_ui(game.words().is_offline(), game.board().word_length(), game.board().max_tries())?;
+        self.draw_logo()
+    }
+
+    fn draw_board(&mut self, guess_list: &[Vec<Letter>]) -> Result<(), RustleError> {
+        self.draw_rows(guess_list, None)?;
+        self.stdout.flush()?;
+
+        Ok(())
+    }
+
+    fn draw_keyboard(&mut self, guess_list: &[Vec<Letter>]) -> Result<(), RustleError> {
+        self.draw_keys(guess_list)?;
+        self.stdout.execute(cursor::Show)?;
+
+        Ok(())
+    }
+
+    fn draw_error(&mut self, error_msg: &str) -> Result<(), RustleError> {
         self.stdout.queue(cursor::MoveUp(cursor::position()?.0 + 2))?;
         self.stdout.queue(terminal::Clear(terminal::ClearType::FromCursorDown))?;
         self.stdout.write_all(format!("{}{}{}", TermFormatter::RedFg.as_str(), error_msg, TermFormatter::Clear.as_str()).as_bytes())?;
@@ -162,13 +294,299 @@ impl RustleDisplay {
         Ok(())
     }
 
-    pub fn terminate_ui(&mut self) -> io::Result<()> {
-        self.stdout.queue(cursor::MoveUp(u16::from(self.overall_height)))?;
-        for _ in 1..=(self.overall_height) {
-            self.stdout.write_all(format!("\n").as_bytes())?;
+    fn draw_prompt(&mut self, prompt: &str) -> Result<(), RustleError> {
+        self.stdout.write_all(format!("{}\n", prompt).as_bytes())?;
+        self.stdout.flush()?;
+
+        Ok(())
+    }
+
+    fn draw_notice(&mut self, message: &str) -> Result<(), RustleError> {
+        self.stdout.write_all(format!("{}\n", message).as_bytes())?;
+        self.stdout.flush()?;
+
+        Ok(())
+    }
+
+    fn draw_warning(&mut self, message: &str) -> Result<(), RustleError> {
+        let color = io::stderr().is_terminal();
+        write_warning(&format!("{}{}{}", TermFormatter::RedBold.as_str_if(color), message, TermFormatter::Clear.as_str_if(color)))
+    }
+
+    // Redraws the board around the typed letters, leaving the cursor at the bottom of the game
+    // canvas. Used by raw-mode input, so every line break has to return the carriage explicitly.
+    fn draw_pending_guess(&mut self, guess_list: &[Vec<Letter>], row: usize, typed: &str, status_msg: &str, is_error: bool) -> Result<(), RustleError> {
+        self.draw_rows(guess_list, Some((row, typed)))?;
+        self.draw_keys(guess_list)?;
+
+        let status_color = if is_error { TermFormatter::RedFg.as_str() } else { TermFormatter::Clear.as_str() };
+        self.stdout.write_all(format!("{}{}{}\r\n\r\n", status_color, status_msg, TermFormatter::Clear.as_str()).as_bytes())?;
+        self.stdout.flush()?;
+
+        Ok(())
+    }
+
+    // Raw-mode input starts right after draw_ui, two lines above where line input would have left
+    // the cursor, so step down over the prompt and input lines to keep the canvas aligned.
+    fn begin_pending_guess(&mut self) -> Result<(), RustleError> {
+        self.raw_mode = Some(RawModeGuard::enable()?);
+
+        self.stdout.write_all("\r\n\r\n".as_bytes())?;
+        self.stdout.flush()?;
+
+        Ok(())
+    }
+
+    fn end_pending_guess(&mut self) -> Result<(), RustleError> {
+        self.raw_mode = None;
+
+        Ok(())
+    }
+
+    // Skips key releases and anything that isn't a key the game uses.
+    fn read_key(&mut self) -> Result<Key, RustleError> {
+        loop {
+            let Event::Key(key) = event::read()? else { continue };
+
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Esc => return Ok(Key::Quit),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(Key::Quit),
+                KeyCode::Char(char) => return Ok(Key::Char(char)),
+                KeyCode::Backspace => return Ok(Key::Backspace),
+                KeyCode::Enter => return Ok(Key::Enter),
+                _ => continue
+            }
         }
-        self.stdout.queue(cursor::RestorePosition)?;
+    }
+
+    fn draw_end_screen(&mut self, end: &GameEnd, solution: &str) -> Result<(), RustleError> {
+        self.stdout.write_all(format!("{}\n", end_message(self.language, end, solution, true)).as_bytes())?;
+
+        self.terminate_ui()
+    }
+
+    fn draw_analysis(&mut self, analysis: &[RowAnalysis]) -> Result<(), RustleError> {
+        write_analysis(&mut self.stdout, analysis, self.language, self.theme, self.depth, true)
+    }
+
+    fn draw_stats(&mut self, stats: &Stats, latest_win: Option<u8>) -> Result<(), RustleError> {
+        write_stats(&mut self.stdout, stats, latest_win, self.language, self.theme, self.depth, true)
+    }
+
+    fn draw_summary(&mut self, share_text: &str, seed: Option<u64>) -> Result<(), RustleError> {
+        write_summary(&mut self.stdout, share_text, seed)
+    }
+}
+
+// Draws a game as plain text, for output that isn't a terminal (or when color is turned off): each
+// guess is printed on its own line once it's scored, with no colors and no cursor movement.
+pub struct PlainDisplay<W: Write> {
+    out: W,
+    language: Language,
+    printed_rows: usize // How many rows have been printed so far.
+}
+
+impl<W: Write> PlainDisplay<W> {
+    pub fn new(out: W, language: Language) -> PlainDisplay<W> {
+        PlainDisplay { out, language, printed_rows: 0 }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> Renderer for PlainDisplay<W> {
+    // Prints the rows scored since the last call, one per line, e.g. "CRANE  G.Y..".
+    fn draw_board(&mut self, guess_list: &[Vec<Letter>]) -> Result<(), RustleError> {
+        for row in guess_list.iter().skip(self.printed_rows) {
+            if row.iter().all(|letter| letter.status == LetterState::NotExists) {
+                break;
+            }
+
+            self.out.write_all(format!("{}\n", plain_row(row)).as_bytes())?;
+            self.printed_rows += 1;
+        }
+
+        self.out.flush()?;
+
+        Ok(())
+    }
+
+    // The rows already say everything the keyboard would.
+    fn draw_keyboard(&mut self, _guess_list: &[Vec<Letter>]) -> Result<(), RustleError> {
+        Ok(())
+    }
+
+    fn draw_error(&mut self, error_msg: &str) -> Result<(), RustleError> {
+        self.out.write_all(error_msg.as_bytes())?;
+        self.out.flush()?;
+
+        Ok(())
+    }
+
+    fn draw_prompt(&mut self, prompt: &str) -> Result<(), RustleError> {
+        self.out.write_all(format!("{}\n", prompt).as_bytes())?;
+        self.out.flush()?;
+
+        Ok(())
+    }
+
+    fn draw_notice(&mut self, message: &str) -> Result<(), RustleError> {
+        self.out.write_all(format!("{}\n", message).as_bytes())?;
+        self.out.flush()?;
+
+        Ok(())
+    }
+
+    fn draw_warning(&mut self, message: &str) -> Result<(), RustleError> {
+        write_warning(message)
+    }
+
+    // Guesses are only ever read a line at a time, so there are no typed letters to show.
+    fn draw_pending_guess(&mut self, _guess_list: &[Vec<Letter>], _row: usize, _typed: &str, _status_msg: &str, _is_error: bool) -> Result<(), RustleError> {
+        Ok(())
+    }
+
+    fn read_key(&mut self) -> Result<Key, RustleError> {
+        Err(RustleError::Terminal(String::from("Plain text output can't read keys, guesses have to be entered a line at a time.")))
+    }
+
+    fn draw_end_screen(&mut self, end: &GameEnd, solution: &str) -> Result<(), RustleError> {
+        self.out.write_all(format!("{}\n", end_message(self.language, end, solution, false)).as_bytes())?;
+        self.out.flush()?;
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    fn draw_analysis(&mut self, analysis: &[RowAnalysis]) -> Result<(), RustleError> {
+        write_analysis(&mut self.out, analysis, self.language, Theme::Monochrome, ColorDepth::Basic, false)
+    }
+
+    fn draw_stats(&mut self, stats: &Stats, latest_win: Option<u8>) -> Result<(), RustleError> {
+        write_stats(&mut self.out, stats, latest_win, self.language, Theme::Monochrome, ColorDepth::Basic, false)
+    }
+
+    fn draw_summary(&mut self, share_text: &str, seed: Option<u64>) -> Result<(), RustleError> {
+        write_summary(&mut self.out, share_text, seed)
+    }
+}
+
+// Warnings go to stderr whichever display is drawing the game.
+fn write_warning(message: &str) -> Result<(), RustleError> {
+    let mut stderr = io::stderr().lock();
+    stderr.write_all(format!("{}\n", message).as_bytes())?;
+    stderr.flush()?;
+
+    Ok(())
+}
+
+// What the end screen says, e.g. "Winner! Word was "CRANE"".
+fn end_message(language: Language, end: &GameEnd, solution: &str, color: bool) -> String {
+    let solution = format!("\"{}{}{}\"", TermFormatter::DefaultBold.as_str_if(color), lang::to_uppercase_word(solution), TermFormatter::Clear.as_str_if(color));
+
+    match end {
+        GameEnd::Won => format!("{}{}{} {} {}", TermFormatter::GreenBold.as_str_if(color), language.winner(), TermFormatter::Clear.as_str_if(color), language.word_was(), solution),
+        GameEnd::Lost(max_tries) => format!("{} {} {}", language.out_of_tries(*max_tries), language.word_was(), solution),
+        GameEnd::Quit => format!("{} {} {}", language.quit(), language.word_was(), solution),
+        GameEnd::AlreadyPlayed => language.already_played(&solution)
+    }
+}
+
+// The post-game review: for each row, how many answers were possible before and after it, the bits
+// of information it gained and the guess the solver would have made. A * marks rows where the
+// player found the solver's pick.
+fn write_analysis(out: &mut impl Write, analysis: &[RowAnalysis], language: Language, theme: Theme, depth: ColorDepth, color: bool) -> Result<(), RustleError> {
+    let board_width = analysis.first().map_or(0, |row| row.guess.chars().count() * 4);
+    let [answers_left, bits, best_guess] = language.analysis_headings();
+
+    out.write_all(format!("\n{}{}{}\n", TermFormatter::DefaultBold.as_str_if(color), language.analysis(), TermFormatter::Clear.as_str_if(color)).as_bytes())?;
+    out.write_all(format!("{:<width$} {:>15} {:>6}   {}\n", "", answers_left, bits, best_guess, width = board_width).as_bytes())?;
+
+    for row in analysis {
+        for (char, status) in row.guess.chars().zip(row.states.iter()) {
+            out.write_all(format!("{} ", theme.tile(lang::to_uppercase(char), status, depth)).as_bytes())?;
+        }
+
+        let best = match &row.best {
+            Some(best) if best.remaining == 1 => format!("{} ({}){}", lang::to_uppercase_word(&best.word), language.the_answer(), if best.word == row.guess { " *" } else { "" }),
+            Some(best) if best.word == row.guess => format!("{} ({:.2} {}) *", lang::to_uppercase_word(&best.word), best.entropy, language.bits()),
+            Some(best) => format!("{} ({:.2} {})", lang::to_uppercase_word(&best.word), best.entropy, language.bits()),
+            None => String::from("-")
+        };
+
+        out.write_all(format!(" {:>6} -> {:<5} {:>6.2}   {}\n", row.before, row.after, row.bits, best).as_bytes())?;
+    }
+
+    out.write_all("\n".as_bytes())?;
+    out.flush()?;
+
+    Ok(())
+}
+
+// The statistics screen, drawn below whatever is currently on the terminal. `latest_win` is the
+// number of guesses in the game that was just won, if any, so its histogram bar can be highlighted.
+fn write_stats(out: &mut impl Write, stats: &Stats, latest_win: Option<u8>, language: Language, theme: Theme, depth: ColorDepth, color: bool) -> Result<(), RustleError> {
+    const MAX_BAR_WIDTH: u32 = 40;
+    const MIN_COLUMN_WIDTHS: [usize; 4] = [8, 8, 18, 14];
+
+    // Each number sits under the right end of its heading, with at least two spaces between headings.
+    let values = [stats.games_played(), stats.win_percentage(), stats.current_streak(), stats.max_streak()];
+    let mut heading_line = String::new();
+    let mut value_line = String::new();
+
+    for (index, heading) in language.stats_headings().iter().enumerate() {
+        let width = MIN_COLUMN_WIDTHS[index].max(heading.chars().count() + 2);
+        heading_line.push_str(&format!("{:>width$}", heading));
+        value_line.push_str(&format!("{:>width$}", values[index]));
+    }
+
+    out.write_all(format!("\n{}{}{}\n", TermFormatter::DefaultBold.as_str_if(color), language.statistics(), TermFormatter::Clear.as_str_if(color)).as_bytes())?;
+    out.write_all(format!("{}\n{}\n\n", heading_line, value_line).as_bytes())?;
+
+    out.write_all(format!("{}{}{}\n", TermFormatter::DefaultBold.as_str_if(color), language.guess_distribution(), TermFormatter::Clear.as_str_if(color)).as_bytes())?;
+
+    let most_wins = stats.guess_distribution().iter().copied().max().unwrap_or(0).max(1);
+
+    for (index, count) in stats.guess_distribution().iter().enumerate() {
+        let bar_width = usize::try_from((count * MAX_BAR_WIDTH).div_ceil(most_wins)).unwrap_or(0);
+        let bar = theme.bar(bar_width, latest_win.map(usize::from) == Some(index + 1), depth);
+
+        out.write_all(format!("{:>3} {} {}\n", index + 1, bar, count).as_bytes())?;
+    }
+
+    out.write_all("\n".as_bytes())?;
+    out.flush()?;
+
+    Ok(())
+}
+
+// The share text for a finished game, plus the command that plays the same seed again.
+fn write_summary(out: &mut impl Write, share_text: &str, seed: Option<u64>) -> Result<(), RustleError> {
+    out.write_all(format!("{}\n", share_text).as_bytes())?;
+
+    if let Some(seed) = seed {
+        out.write_all(format!("\nPlayed seed {}, challenge a friend with \"rustle --seed {}\"\n", seed, seed).as_bytes())?;
+    }
+
+    out.flush()?;
+
+    Ok(())
+}
+
+// A scored row as plain text: the guess, then G for each green letter, Y for each yellow one and .
+// for the rest, e.g. "CRANE  G.Y..".
+pub fn plain_row(row: &[Letter]) -> String {
+    let guess: String = row.iter().map(|letter| lang::to_uppercase(letter.value)).collect();
+    let colors: String = row.iter().map(|letter| match letter.status {
+        LetterState::Correct => 'G',
+        LetterState::Exists => 'Y',
+        _ => '.'
+    }).collect();
+
+    format!("{}  {}", guess, colors)
+}
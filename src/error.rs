@@ -0,0 +1,50 @@
+use std::{error::Error, fmt, io};
+
+use crate::game::GuessError;
+
+// Everything that can go wrong in Rustle. Each variant carries a message that can be shown as is,
+// the binary adds what kind of failure it was and picks an exit code from the variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RustleError {
+    Network(String), // The NYT couldn't be reached or didn't have the puzzle.
+    Parse(String), // Malformed JSON, dates, numbers or names.
+    Wordlist(String), // The word lists or a solution picked from them can't be used.
+    Terminal(String), // Drawing to or reading from the terminal failed.
+    InvalidGuess(GuessError),
+    Storage(String) // Reading or writing stats, cached solutions or other files failed.
+}
+
+impl fmt::Display for RustleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RustleError::Network(msg)
+                | RustleError::Parse(msg)
+                | RustleError::Wordlist(msg)
+                | RustleError::Terminal(msg)
+                | RustleError::Storage(msg) => write!(f, "{}", msg),
+            RustleError::InvalidGuess(err) => write!(f, "{}", err)
+        }
+    }
+}
+
+impl Error for RustleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RustleError::InvalidGuess(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+impl From<GuessError> for RustleError {
+    fn from(err: GuessError) -> RustleError {
+        RustleError::InvalidGuess(err)
+    }
+}
+
+// Bare IO errors only come from the terminal, file access always says which file it was about.
+impl From<io::Error> for RustleError {
+    fn from(err: io::Error) -> RustleError {
+        RustleError::Terminal(err.to_string())
+    }
+}
@@ -0,0 +1,163 @@
+use std::{error::Error, fmt};
+
+use crate::lang::Language;
+use crate::words::WordleWords;
+use crate::{Board, Letter, LetterState, check_hard_mode, process_guess, score_guess};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    InProgress,
+    Won(u8), // The number of guesses the solution was found in.
+    Lost
+}
+
+// Why a guess was turned away. Rejected guesses don't use up an attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError {
+    GameOver,
+    WrongLength { expected: usize, actual: usize },
+    InvalidWord(String),
+    HardMode(HardModeViolation)
+}
+
+// The first hint a hard mode guess ignored, see check_hard_mode. Letters are lowercase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HardModeViolation {
+    WrongLetter { position: usize, letter: char }, // A green letter moved, with its position counting from 1.
+    MissingLetter { letter: char, count: usize } // A revealed letter used fewer than `count` times.
+}
+
+// The English message, Language::guess_error has the others.
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Language::English.guess_error(self))
+    }
+}
+
+impl Error for GuessError {}
+
+// The scored row for an accepted guess, along with where it left the game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowResult {
+    pub guess: String,
+    pub states: Vec<LetterState>,
+    pub attempt: u8,
+    pub outcome: Outcome
+}
+
+// A single game of Rustle: the words and solution, the board played so far and the outcome. All of
+// the rules live here so a game can be played start to finish without a terminal.
+pub struct Game {
+    words: WordleWords,
+    board: Board,
+    hard_mode: bool,
+    attempts: u8,
+    outcome: Outcome
+}
+
+impl Game {
+    pub fn new(words: WordleWords, max_tries: u8, hard_mode: bool) -> Game {
+        let board = Board::new(words.get_word_length(), max_tries);
+
+        Game { words, board, hard_mode, attempts: 0, outcome: Outcome::InProgress }
+    }
+
+    // Checks that a guess could be submitted right now without playing it.
+    pub fn validate(&self, guess: &str) -> Result<(), GuessError> {
+        if self.outcome != Outcome::InProgress {
+            return Err(GuessError::GameOver)
+        }
+
+        let word_length = self.words.get_word_length();
+        let guess_length = guess.chars().count();
+
+        if guess_length != word_length {
+            return Err(GuessError::WrongLength { expected: word_length, actual: guess_length })
+        }
+
+        let language = self.words.get_language();
+
+        if !guess.chars().all(|letter| language.is_letter(letter)) || !self.words.is_valid_guess(guess) {
+            return Err(GuessError::InvalidWord(guess.to_string()))
+        }
+
+        if self.hard_mode {
+            check_hard_mode(guess, self.board.rows())?;
+        }
+
+        Ok(())
+    }
+
+    // Scores a guess onto the next row of the board and moves the game on.
+    pub fn submit(&mut self, guess: &str) -> Result<RowResult, GuessError> {
+        self.validate(guess)?;
+
+        let guess = guess.to_lowercase();
+        let solution = self.words.get_solution().clone();
+
+        self.attempts += 1;
+        process_guess(&guess, &mut self.board, &solution, self.attempts);
+
+        if guess == solution {
+            self.outcome = Outcome::Won(self.attempts);
+        } else if self.attempts == self.board.max_tries() {
+            self.outcome = Outcome::Lost;
+        }
+
+        Ok(RowResult {
+            states: score_guess(&guess, &solution),
+            guess,
+            attempt: self.attempts,
+            outcome: self.outcome
+        })
+    }
+
+    pub fn words(&self) -> &WordleWords {
+        &self.words
+    }
+
+    // Hands the words back so a new game can be started with the same solution.
+    pub fn into_words(self) -> WordleWords {
+        self.words
+    }
+
+    pub fn solution(&self) -> &str {
+        self.words.get_solution()
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn rows(&self) -> &[Vec<Letter>] {
+        self.board.rows()
+    }
+
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    // The guesses played so far, in order.
+    pub fn guesses(&self) -> Vec<String> {
+        self.board.rows().iter()
+            .take(usize::from(self.attempts))
+            .map(|row| row.iter().map(|letter| letter.value()).collect())
+            .collect()
+    }
+
+    pub fn attempts(&self) -> u8 {
+        self.attempts
+    }
+
+    pub fn max_tries(&self) -> u8 {
+        self.board.max_tries()
+    }
+
+    pub fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.outcome != Outcome::InProgress
+    }
+}
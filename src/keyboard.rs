@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use crate::{Letter, LetterState};
+use crate::lang;
+
+pub const QWERTY_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+// Accented vowels get a row of their own, since they're separate letters in the word packs.
+pub const SPANISH_ROWS: [&str; 4] = ["qwertyuiop", "asdfghjklñ", "zxcvbnm", "áéíóúü"];
+pub const QWERTZ_ROWS: [&str; 3] = ["qwertzuiopü", "asdfghjklöä", "yxcvbnmß"];
+pub const AZERTY_ROWS: [&str; 4] = ["azertyuiop", "qsdfghjklm", "wxcvbn", "àâçéèêëîïôû"];
+
+// How much a state tells the player about a letter, so the keyboard can show the best one seen.
+fn state_rank(status: &LetterState) -> u8 {
+    match status {
+        LetterState::Correct => 3,
+        LetterState::Exists => 2,
+        LetterState::Incorrect => 1,
+        LetterState::NotExists => 0
+    }
+}
+
+// Aggregates every scored letter in the guess list into the best state seen for each key: green
+// beats yellow beats gray. Letters that haven't been guessed yet are left out of the map.
+pub fn key_states(guess_list: &[Vec<Letter>]) -> HashMap<char, LetterState> {
+    let mut states: HashMap<char, LetterState> = HashMap::new();
+
+    for letter in guess_list.iter().flatten() {
+        if letter.status() == &LetterState::NotExists {
+            continue;
+        }
+
+        let key = lang::to_lowercase(letter.value());
+        let best = match states.get(&key) {
+            Some(seen) if state_rank(seen) >= state_rank(letter.status()) => *seen,
+            _ => *letter.status()
+        };
+
+        states.insert(key, best);
+    }
+
+    states
+}
+
+// The keyboard laid out as rows of letters, each paired with its aggregated state.
+pub fn keyboard_rows(guess_list: &[Vec<Letter>], layout: &[&str]) -> Vec<Vec<Letter>> {
+    let states = key_states(guess_list);
+
+    layout.iter().map(|row| {
+        row.chars().map(|key| Letter::new(key, *states.get(&key).unwrap_or(&LetterState::NotExists))).collect()
+    }).collect()
+}
@@ -0,0 +1,411 @@
+use std::str::FromStr;
+
+use crate::error::RustleError;
+use crate::game::{GuessError, HardModeViolation};
+use crate::keyboard;
+
+// The language a game is played in, which picks the word pack, the letters that can be typed, the
+// on-screen keyboard and the language of the prompts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    German,
+    French
+}
+
+// The prompts and screens shown while playing, see Language::text.
+struct Text {
+    type_prompt: &'static str,
+    enter_prompt: &'static str,
+    retry_prompt: &'static str,
+    not_enough_letters: &'static str,
+    too_many_letters: &'static str,
+    invalid_word: &'static str, // Followed by the rejected guess in quotes.
+    game_over: &'static str,
+    hard_mode: &'static str, // Followed by what the guess is missing.
+    wrong_letter: &'static str, // With {position} from ordinal and {letter} filled in.
+    missing_letter: &'static str, // With {letter} filled in.
+    missing_letters: &'static str, // With {count} and {letter} filled in.
+    ordinal: fn(usize) -> String, // A position in the word, e.g. "2nd".
+    winner: &'static str,
+    quit: &'static str,
+    word_was: &'static str,
+    out_of_tries: (&'static str, &'static str), // Either side of the number of tries.
+    already_played: (&'static str, &'static str), // Either side of the solution.
+    hint: &'static str, // With {word}, {bits} and {count} filled in.
+    only_answer: &'static str, // With {word} filled in.
+    no_hint: &'static str,
+    analysis: &'static str,
+    answers_left: &'static str, // The analysis columns, kept short enough to line up.
+    bits_heading: &'static str,
+    best_guess: &'static str,
+    the_answer: &'static str,
+    bits: &'static str,
+    statistics: &'static str,
+    stats_headings: [&'static str; 4], // Played, win %, current streak and max streak.
+    guess_distribution: &'static str
+}
+
+const ENGLISH_TEXT: Text = Text {
+    type_prompt: "Type a word guess, Enter to submit, ? for a hint, Esc to quit:",
+    enter_prompt: "Enter a word guess:",
+    retry_prompt: "! Please enter a new guess:",
+    not_enough_letters: "Not enough letters",
+    too_many_letters: "Too many letters",
+    invalid_word: "Invalid word",
+    game_over: "The game is already over",
+    hard_mode: "Hard mode:",
+    wrong_letter: "{position} letter must be {letter}",
+    missing_letter: "guess must contain {letter}",
+    missing_letters: "guess must contain {count} {letter}'s",
+    ordinal: english_ordinal,
+    winner: "WINNER!",
+    quit: "Quit!",
+    word_was: "Word was",
+    out_of_tries: ("Failed to guess in", "tries!"),
+    already_played: ("You've already played this puzzle! Word was", ", use --new to play it again."),
+    hint: "Try {word}: {bits} bits expected, {count} possible answers left",
+    only_answer: "It can only be {word}",
+    no_hint: "No word in the word list fits the feedback so far",
+    analysis: "ANALYSIS",
+    answers_left: "Answers left",
+    bits_heading: "Bits",
+    best_guess: "Best guess",
+    the_answer: "the answer",
+    bits: "bits",
+    statistics: "STATISTICS",
+    stats_headings: ["Played", "Win %", "Current Streak", "Max Streak"],
+    guess_distribution: "GUESS DISTRIBUTION"
+};
+
+const SPANISH_TEXT: Text = Text {
+    type_prompt: "Escribe una palabra, Enter para enviar, ? para una pista, Esc para salir:",
+    enter_prompt: "Escribe una palabra:",
+    retry_prompt: ". Escribe otra palabra:",
+    not_enough_letters: "Faltan letras",
+    too_many_letters: "Sobran letras",
+    invalid_word: "Palabra no válida",
+    game_over: "La partida ya ha terminado",
+    hard_mode: "Modo difícil:",
+    wrong_letter: "la {position} letra debe ser {letter}",
+    missing_letter: "la palabra debe contener {letter}",
+    missing_letters: "la palabra debe contener {count} {letter}",
+    ordinal: |position| format!("{}.ª", position),
+    winner: "¡GANASTE!",
+    quit: "¡Abandonaste!",
+    word_was: "La palabra era",
+    out_of_tries: ("¡No la adivinaste en", "intentos!"),
+    already_played: ("¡Ya jugaste esta partida! La palabra era", ", usa --new para jugarla otra vez."),
+    hint: "Prueba {word}: {bits} bits esperados, quedan {count} respuestas posibles",
+    only_answer: "Solo puede ser {word}",
+    no_hint: "Ninguna palabra de la lista encaja con las pistas hasta ahora",
+    analysis: "ANÁLISIS",
+    answers_left: "Posibles",
+    bits_heading: "Bits",
+    best_guess: "Mejor jugada",
+    the_answer: "la respuesta",
+    bits: "bits",
+    statistics: "ESTADÍSTICAS",
+    stats_headings: ["Jugadas", "% Ganadas", "Racha actual", "Mejor racha"],
+    guess_distribution: "DISTRIBUCIÓN DE INTENTOS"
+};
+
+const GERMAN_TEXT: Text = Text {
+    type_prompt: "Gib ein Wort ein, Enter zum Absenden, ? für einen Tipp, Esc zum Beenden:",
+    enter_prompt: "Gib ein Wort ein:",
+    retry_prompt: "! Bitte gib ein neues Wort ein:",
+    not_enough_letters: "Zu wenige Buchstaben",
+    too_many_letters: "Zu viele Buchstaben",
+    invalid_word: "Ungültiges Wort",
+    game_over: "Das Spiel ist schon vorbei",
+    hard_mode: "Schwerer Modus:",
+    wrong_letter: "der {position} Buchstabe muss {letter} sein",
+    missing_letter: "das Wort muss {letter} enthalten",
+    missing_letters: "das Wort muss {count}× {letter} enthalten",
+    ordinal: |position| format!("{}.", position),
+    winner: "GEWONNEN!",
+    quit: "Beendet!",
+    word_was: "Das Wort war",
+    out_of_tries: ("Nicht in", "Versuchen erraten!"),
+    already_played: ("Dieses Rätsel hast du schon gespielt! Das Wort war", ", mit --new spielst du es noch einmal."),
+    hint: "Versuch es mit {word}: {bits} Bits erwartet, {count} mögliche Lösungen übrig",
+    only_answer: "Es kann nur {word} sein",
+    no_hint: "Kein Wort der Liste passt zu den bisherigen Hinweisen",
+    analysis: "ANALYSE",
+    answers_left: "Verbleibend",
+    bits_heading: "Bits",
+    best_guess: "Bester Tipp",
+    the_answer: "die Lösung",
+    bits: "Bits",
+    statistics: "STATISTIK",
+    stats_headings: ["Gespielt", "Siege %", "Aktuelle Serie", "Längste Serie"],
+    guess_distribution: "VERTEILUNG DER VERSUCHE"
+};
+
+const FRENCH_TEXT: Text = Text {
+    type_prompt: "Tapez un mot, Entrée pour valider, ? pour un indice, Échap pour quitter :",
+    enter_prompt: "Entrez un mot :",
+    retry_prompt: " ! Entrez un nouveau mot :",
+    not_enough_letters: "Pas assez de lettres",
+    too_many_letters: "Trop de lettres",
+    invalid_word: "Mot invalide",
+    game_over: "La partie est déjà terminée",
+    hard_mode: "Mode difficile :",
+    wrong_letter: "la {position} lettre doit être {letter}",
+    missing_letter: "le mot doit contenir {letter}",
+    missing_letters: "le mot doit contenir {count} {letter}",
+    ordinal: |position| if position == 1 { String::from("1re") } else { format!("{}e", position) },
+    winner: "GAGNÉ !",
+    quit: "Abandon !",
+    word_was: "Le mot était",
+    out_of_tries: ("Pas trouvé en", "essais !"),
+    already_played: ("Vous avez déjà joué cette grille ! Le mot était", ", utilisez --new pour la rejouer."),
+    hint: "Essayez {word} : {bits} bits attendus, {count} réponses possibles restantes",
+    only_answer: "Ce ne peut être que {word}",
+    no_hint: "Aucun mot de la liste ne correspond aux indices jusqu'ici",
+    analysis: "ANALYSE",
+    answers_left: "Restantes",
+    bits_heading: "Bits",
+    best_guess: "Meilleur mot",
+    the_answer: "la réponse",
+    bits: "bits",
+    statistics: "STATISTIQUES",
+    stats_headings: ["Parties", "% Victoires", "Série actuelle", "Meilleure série"],
+    guess_distribution: "RÉPARTITION DES ESSAIS"
+};
+
+fn english_ordinal(position: usize) -> String {
+    let suffix = match (position % 10, position % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th"
+    };
+
+    format!("{}{}", position, suffix)
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [Language::English, Language::Spanish, Language::German, Language::French];
+
+    // The code given to --lang.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::German => "de",
+            Language::French => "fr"
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Spanish",
+            Language::German => "German",
+            Language::French => "French"
+        }
+    }
+
+    // Every lowercase letter that can appear in a word. Accented letters are letters in their own
+    // right, so an E never matches an É.
+    pub fn alphabet(&self) -> &'static str {
+        match self {
+            Language::English => "abcdefghijklmnopqrstuvwxyz",
+            Language::Spanish => "abcdefghijklmnñopqrstuvwxyzáéíóúü",
+            Language::German => "abcdefghijklmnopqrstuvwxyzäöüß",
+            Language::French => "abcdefghijklmnopqrstuvwxyzàâçéèêëîïôû"
+        }
+    }
+
+    // Whether a typed character is a letter of this language, in either case.
+    pub fn is_letter(&self, letter: char) -> bool {
+        self.alphabet().contains(to_lowercase(letter))
+    }
+
+    pub fn keyboard(&self) -> &'static [&'static str] {
+        match self {
+            Language::English => &keyboard::QWERTY_ROWS,
+            Language::Spanish => &keyboard::SPANISH_ROWS,
+            Language::German => &keyboard::QWERTZ_ROWS,
+            Language::French => &keyboard::AZERTY_ROWS
+        }
+    }
+
+    fn text(&self) -> &'static Text {
+        match self {
+            Language::English => &ENGLISH_TEXT,
+            Language::Spanish => &SPANISH_TEXT,
+            Language::German => &GERMAN_TEXT,
+            Language::French => &FRENCH_TEXT
+        }
+    }
+
+    // The prompt for typing straight into the tiles.
+    pub fn type_prompt(&self) -> &'static str {
+        self.text().type_prompt
+    }
+
+    // The prompt for reading a guess a line at a time.
+    pub fn enter_prompt(&self) -> &'static str {
+        self.text().enter_prompt
+    }
+
+    pub fn retry_prompt(&self, error: &GuessError) -> String {
+        format!("{}{}", self.guess_error(error), self.text().retry_prompt)
+    }
+
+    // Why a guess was turned away.
+    pub fn guess_error(&self, error: &GuessError) -> String {
+        let text = self.text();
+
+        match error {
+            GuessError::GameOver => text.game_over.to_string(),
+            GuessError::WrongLength { expected, actual } if actual < expected => text.not_enough_letters.to_string(),
+            GuessError::WrongLength { .. } => text.too_many_letters.to_string(),
+            GuessError::InvalidWord(guess) => format!("{} \"{}\"", text.invalid_word, guess),
+            GuessError::HardMode(violation) => format!("{} {}", text.hard_mode, self.hard_mode_violation(violation))
+        }
+    }
+
+    fn hard_mode_violation(&self, violation: &HardModeViolation) -> String {
+        let text = self.text();
+
+        match *violation {
+            HardModeViolation::WrongLetter { position, letter } => text.wrong_letter
+                .replace("{position}", &(text.ordinal)(position))
+                .replace("{letter}", &to_uppercase(letter).to_string()),
+            HardModeViolation::MissingLetter { letter, count: 1 } => text.missing_letter
+                .replace("{letter}", &to_uppercase(letter).to_string()),
+            HardModeViolation::MissingLetter { letter, count } => text.missing_letters
+                .replace("{count}", &count.to_string())
+                .replace("{letter}", &to_uppercase(letter).to_string())
+        }
+    }
+
+    pub fn winner(&self) -> &'static str {
+        self.text().winner
+    }
+
+    pub fn quit(&self) -> &'static str {
+        self.text().quit
+    }
+
+    pub fn word_was(&self) -> &'static str {
+        self.text().word_was
+    }
+
+    pub fn out_of_tries(&self, max_tries: u8) -> String {
+        let (before, after) = self.text().out_of_tries;
+
+        format!("{} {} {}", before, max_tries, after)
+    }
+
+    // For a finished daily puzzle that was launched again, with the solution already formatted.
+    pub fn already_played(&self, solution: &str) -> String {
+        let (before, after) = self.text().already_played;
+
+        format!("{} {}{}", before, solution, after)
+    }
+
+    // The guess a hint suggests, the information it's expected to give and the answers still possible.
+    pub fn hint(&self, word: &str, entropy: f64, remaining: usize) -> String {
+        self.text().hint
+            .replace("{word}", &to_uppercase_word(word))
+            .replace("{bits}", &format!("{:.2}", entropy))
+            .replace("{count}", &remaining.to_string())
+    }
+
+    // A hint when there's only one answer left.
+    pub fn only_answer(&self, word: &str) -> String {
+        self.text().only_answer.replace("{word}", &to_uppercase_word(word))
+    }
+
+    // When nothing in the word list fits the colors so far.
+    pub fn no_hint(&self) -> &'static str {
+        self.text().no_hint
+    }
+
+    // The post-game review's title.
+    pub fn analysis(&self) -> &'static str {
+        self.text().analysis
+    }
+
+    // The post-game review's answers left, bits and best guess columns.
+    pub fn analysis_headings(&self) -> [&'static str; 3] {
+        let text = self.text();
+
+        [text.answers_left, text.bits_heading, text.best_guess]
+    }
+
+    // Said of the solver's pick when it could only have been the answer.
+    pub fn the_answer(&self) -> &'static str {
+        self.text().the_answer
+    }
+
+    // The unit after a number of bits.
+    pub fn bits(&self) -> &'static str {
+        self.text().bits
+    }
+
+    pub fn statistics(&self) -> &'static str {
+        self.text().statistics
+    }
+
+    // The games played, win %, current streak and max streak columns of the statistics screen.
+    pub fn stats_headings(&self) -> [&'static str; 4] {
+        self.text().stats_headings
+    }
+
+    pub fn guess_distribution(&self) -> &'static str {
+        self.text().guess_distribution
+    }
+}
+
+impl FromStr for Language {
+    type Err = RustleError;
+
+    fn from_str(code: &str) -> Result<Language, RustleError> {
+        Language::ALL.into_iter()
+            .find(|language| language.code() == code.to_lowercase())
+            .ok_or_else(|| RustleError::Parse(format!(
+                "Unknown language \"{}\", expected one of: {}",
+                code,
+                Language::ALL.map(|language| language.code()).join(", ")
+            )))
+    }
+}
+
+// The lowercase form of a single letter. Letters whose lowercase takes more than one character
+// aren't in any alphabet, so they're left as they are.
+pub fn to_lowercase(letter: char) -> char {
+    let mut lower = letter.to_lowercase();
+
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => lower,
+        _ => letter
+    }
+}
+
+// The uppercase form of a letter for a tile or key. ß has no single-letter uppercase in Rust's
+// mapping (it becomes "SS"), so it's shown as the capital ẞ to keep one letter per tile.
+pub fn to_uppercase(letter: char) -> char {
+    if letter == 'ß' {
+        return 'ẞ'
+    }
+
+    let mut upper = letter.to_uppercase();
+
+    match (upper.next(), upper.next()) {
+        (Some(upper), None) => upper,
+        _ => letter
+    }
+}
+
+// A whole word in uppercase, letter by letter, e.g. "STRAẞE".
+pub fn to_uppercase_word(word: &str) -> String {
+    word.chars().map(to_uppercase).collect()
+}
@@ -1,13 +1,29 @@
+pub mod bench;
+pub mod cache;
+pub mod cli;
+pub mod config;
 pub mod display;
+pub mod error;
+pub mod game;
+pub mod keyboard;
+pub mod lang;
+pub mod render;
+pub mod save;
+pub mod share;
+pub mod solver;
+pub mod source;
+pub mod stats;
+pub mod storage;
+pub mod theme;
 pub mod words;
 
-use std::{error::Error, io::BufRead};
-use lazy_static::lazy_static;
-use regex::Regex;
+use std::{collections::HashMap, io::BufRead};
 
-use display::{TermFormatter, RustleDisplay};
+use error::RustleError;
+use game::{Game, GuessError, HardModeViolation};
+use render::{Key, Renderer};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LetterState {
     Exists,
     NotExists,
@@ -15,6 +31,7 @@ pub enum LetterState {
     Incorrect
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Letter {
     value: char,
     status: LetterState
@@ -22,7 +39,7 @@ pub struct Letter {
 
 impl Letter {
     pub fn new(value: char, status: LetterState) -> Letter {
-        Letter { value: value, status: status }
+        Letter { value, status }
     }
 
     pub fn value(&self) -> char {
@@ -32,69 +49,205 @@ impl Letter {
     pub fn status(&self) -> &LetterState {
         &self.status
     }
+}
 
-    pub fn get_ansi_color(&self) -> String {
-        match self.status {
-            LetterState::Correct => TermFormatter::GreenBg.as_str(),
-            LetterState::Exists => TermFormatter::YellowBg.as_str(),
-            LetterState::Incorrect => TermFormatter::GrayBg.as_str(),
-            LetterState::NotExists => TermFormatter::WhiteBg.as_str()
-        }
+pub const DEFAULT_TRIES: u8 = 6;
+pub const MAX_ALLOWED_TRIES: u8 = 12;
+
+// The guess rows of a game, sized at runtime from the word length and the number of tries. Rows
+// that haven't been played yet are filled with blank NotExists placeholders.
+pub struct Board {
+    rows: Vec<Vec<Letter>>,
+    word_length: usize,
+    max_tries: u8 // Always rows.len(), kept as a u8 so it never has to be narrowed back down.
+}
+
+impl Board {
+    pub fn new(word_length: usize, max_tries: u8) -> Board {
+        let rows = (0..max_tries).map(|_| {
+            (0..word_length).map(|_| Letter::new(' ', LetterState::NotExists)).collect()
+        }).collect();
+
+        Board { rows, word_length, max_tries }
+    }
+
+    pub fn rows(&self) -> &[Vec<Letter>] {
+        &self.rows
+    }
+
+    pub fn word_length(&self) -> usize {
+        self.word_length
+    }
+
+    pub fn max_tries(&self) -> u8 {
+        self.max_tries
+    }
+
+    pub fn set_row(&mut self, index: usize, row: Vec<Letter>) {
+        self.rows[index] = row;
     }
 }
 
-pub fn get_user_guess<R>(stdin: &mut R, display_man: &mut RustleDisplay, wordle_words: &words::WordleWords) -> Result<String, Box<dyn Error>>
+pub fn get_user_guess<R, D>(stdin: &mut R, renderer: &mut D, game: &Game) -> Result<String, RustleError>
     where
-        R: BufRead
+        R: BufRead,
+        D: Renderer + ?Sized
     {
-    const WORD_GUESS_PROMPT: &str = "Enter a word guess:";
+    let language = game.words().get_language();
 
     let mut guess = String::new();
 
-    println!("{}", WORD_GUESS_PROMPT);
-    stdin.read_line(&mut guess)?;
-
-    guess = guess.trim().to_string();
-
-    lazy_static! {
-        static ref RE: Regex = Regex::new("^[a-zA-Z]{5}$").expect("Faied to create RegEx");
+    renderer.draw_prompt(language.enter_prompt())?;
+    if stdin.read_line(&mut guess)? == 0 {
+        return Err(RustleError::Terminal(String::from("Reached the end of input before a guess was entered.")))
     }
 
-    while !RE.is_match(&guess) || !wordle_words.get_wordlist().contains(&String::from(&guess)) {
+    guess = guess.trim().to_string();
 
-        display_man.draw_input_error(format!("Invalid word \"{}\"! Please enter a new guess:\n", &guess).as_str())?;
+    while let Err(error) = game.validate(&guess) {
+        renderer.draw_error(format!("{}\n", language.retry_prompt(&error)).as_str())?;
 
         guess.clear();
 
-        stdin.read_line(&mut guess)?;
+        if stdin.read_line(&mut guess)? == 0 {
+            return Err(RustleError::Terminal(String::from("Reached the end of input before a guess was entered.")))
+        }
         guess = guess.trim().to_string();
     }
 
     Ok(guess.to_lowercase())
 }
 
-pub fn process_guess(user_guess: &str, guess_list: &mut [Vec<Letter>; 6], solution: &str, attempt: u8) {
+// Reads a guess straight into the current row of tiles, a key at a time: letters fill the tiles,
+// Backspace deletes, Enter submits, ? shows a hint and Esc (or Ctrl+C) quits, returning None.
+pub fn get_user_guess_interactive<D: Renderer + ?Sized>(renderer: &mut D, game: &Game) -> Result<Option<String>, RustleError> {
+    renderer.begin_pending_guess()?;
+    let guess = read_pending_guess(renderer, game);
+    renderer.end_pending_guess()?;
+
+    guess
+}
+
+fn read_pending_guess<D: Renderer + ?Sized>(renderer: &mut D, game: &Game) -> Result<Option<String>, RustleError> {
+    let language = game.words().get_language();
+    let row = usize::from(game.attempts());
+    let word_length = game.words().get_word_length();
+
+    let mut typed = String::new();
+    let mut status_msg = String::from(language.type_prompt());
+    let mut is_error = false;
+
+    loop {
+        renderer.draw_pending_guess(game.rows(), row, &typed, &status_msg, is_error)?;
+
+        match renderer.read_key()? {
+            Key::Quit => return Ok(None),
+            Key::Char(char) if language.is_letter(char) && typed.chars().count() < word_length => {
+                typed.push(lang::to_lowercase(char));
+            },
+            Key::Backspace => {
+                typed.pop();
+            },
+            Key::Char('?') => {
+                status_msg = solver::describe_hint(game);
+                is_error = false;
+                continue;
+            },
+            Key::Enter => {
+                match game.validate(&typed) {
+                    Ok(()) => return Ok(Some(typed)),
+                    Err(error) => {
+                        status_msg = language.guess_error(&error);
+                        is_error = true;
+                        continue;
+                    }
+                }
+            },
+            Key::Char(_) => continue
+        }
+
+        status_msg = String::from(language.type_prompt());
+        is_error = false;
+    }
+}
+
+// Checks a guess against every hint revealed so far: green letters must stay in place and every
+// revealed letter must be reused at least as many times as a single row showed it. Rows that
+// haven't been played yet (all NotExists) are ignored. The error describes the first violation.
+pub fn check_hard_mode(user_guess: &str, guess_list: &[Vec<Letter>]) -> Result<(), GuessError> {
+    let user_guess_chars: Vec<char> = user_guess.to_lowercase().chars().collect();
+
+    for row in guess_list {
+        for (index, letter) in row.iter().enumerate() {
+            if letter.status == LetterState::Correct && user_guess_chars.get(index) != Some(&letter.value) {
+                return Err(GuessError::HardMode(HardModeViolation::WrongLetter { position: index + 1, letter: letter.value }))
+            }
+        }
+    }
+
+    for row in guess_list {
+        let mut revealed: Vec<char> = Vec::new();
+
+        for letter in row {
+            if (letter.status == LetterState::Correct || letter.status == LetterState::Exists) && !revealed.contains(&letter.value) {
+                revealed.push(letter.value);
+            }
+        }
+
+        for char in revealed {
+            let required = row.iter().filter(|letter| letter.value == char && (letter.status == LetterState::Correct || letter.status == LetterState::Exists)).count();
+            let used = user_guess_chars.iter().filter(|guess_char| **guess_char == char).count();
 
+            if used < required {
+                return Err(GuessError::HardMode(HardModeViolation::MissingLetter { letter: char, count: required }))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Scores a guess against the solution the way Wordle does: exact matches are marked first, then
+// misplaced letters are only marked while the solution still has unmatched copies of that letter,
+// so any excess copies in the guess come back as Incorrect.
+pub fn score_guess(user_guess: &str, solution: &str) -> Vec<LetterState> {
     let user_guess_chars: Vec<char> = user_guess.chars().collect();
     let solution_chars: Vec<char> = solution.chars().collect();
 
-    let mut current_guess: Vec<Letter> = Vec::new();
+    let mut states: Vec<LetterState> = vec![LetterState::Incorrect; user_guess_chars.len()];
+    let mut unmatched: HashMap<char, usize> = HashMap::new();
 
+    for (index, char) in solution_chars.iter().enumerate() {
+        if user_guess_chars.get(index) == Some(char) {
+            states[index] = LetterState::Correct;
+        } else {
+            *unmatched.entry(*char).or_insert(0) += 1;
+        }
+    }
 
     for (index, char) in user_guess_chars.iter().enumerate() {
-        let status: LetterState;
-        if char == &solution_chars[index] {
-            status = LetterState::Correct;
-        } else if solution_chars.contains(char) {
-            status = LetterState::Exists;
-        } else {
-            status = LetterState::Incorrect;
+        if states[index] == LetterState::Correct {
+            continue;
         }
 
-        current_guess.push(Letter::new(*char, status))
+        if let Some(count) = unmatched.get_mut(char) {
+            if *count > 0 {
+                *count -= 1;
+                states[index] = LetterState::Exists;
+            }
+        }
     }
 
-    guess_list[usize::from(attempt)-1] = current_guess;
+    states
+}
+
+pub fn process_guess(user_guess: &str, board: &mut Board, solution: &str, attempt: u8) {
+    let current_guess: Vec<Letter> = user_guess.chars()
+        .zip(score_guess(user_guess, solution))
+        .map(|(char, status)| Letter::new(char, status))
+        .collect();
+
+    board.set_row(usize::from(attempt)-1, current_guess);
 }
 
 #[cfg(test)]
@@ -1,14 +1,25 @@
 use std::fs;
+use std::error::Error;
 
 use serde_json::Value;
 
 use super::*;
 use std::collections::HashSet;
+use std::ffi::OsString;
+use std::io::{BufReader, Write};
+use std::net::TcpListener;
+use std::thread;
+use source::SolutionSource;
+use display::TermFormatter;
+use game::HardModeViolation;
+use render::{GameEnd, Key, RecordingRenderer, RenderEvent, Renderer};
+use proptest::prelude::*;
+use lazy_static::lazy_static;
 
 #[test]
 fn create_new_letter() {
     const TEST_LETTER: char = 'a';
-    let new_letter = Letter::new(TEST_LETTER.clone(), LetterState::Correct);
+    let new_letter = Letter::new(TEST_LETTER, LetterState::Correct);
 
     assert_eq!(new_letter.value, TEST_LETTER);
     assert_eq!(new_letter.status, LetterState::Correct)
@@ -18,42 +29,50 @@ fn create_new_letter() {
 fn get_letter_color() {
     const TEST_LETTER: char = 'a';
 
-    let new_letter_one = Letter::new(TEST_LETTER.clone(), LetterState::Correct);
+    let new_letter_one = Letter::new(TEST_LETTER, LetterState::Correct);
 
-    let new_letter_two = Letter::new(TEST_LETTER.clone(), LetterState::Incorrect);
+    let new_letter_two = Letter::new(TEST_LETTER, LetterState::Incorrect);
 
-    let new_letter_three = Letter::new(TEST_LETTER.clone(), LetterState::Exists);
+    let new_letter_three = Letter::new(TEST_LETTER, LetterState::Exists);
 
-    let new_letter_four = Letter::new(TEST_LETTER.clone(), LetterState::NotExists);
+    let new_letter_four = Letter::new(TEST_LETTER, LetterState::NotExists);
 
-    assert_eq!(new_letter_one.get_ansi_color(), TermFormatter::GreenBg.as_str());
-    assert_eq!(new_letter_two.get_ansi_color(), TermFormatter::GrayBg.as_str());
-    assert_eq!(new_letter_three.get_ansi_color(), TermFormatter::YellowBg.as_str());
-    assert_eq!(new_letter_four.get_ansi_color(), TermFormatter::WhiteBg.as_str());
+    let tile = |letter: &Letter| theme::Theme::Default.tile(letter.value(), letter.status(), theme::ColorDepth::Basic);
+
+    assert!(tile(&new_letter_one).contains(&TermFormatter::GreenBg.as_str()));
+    assert!(tile(&new_letter_two).contains(&TermFormatter::GrayBg.as_str()));
+    assert!(tile(&new_letter_three).contains(&TermFormatter::YellowBg.as_str()));
+    assert!(tile(&new_letter_four).contains(&TermFormatter::WhiteBg.as_str()));
 }
 
 
 struct TestWordList {
-    wordlist: Vec<String>
+    wordlist: Vec<String>,
+    answerlist: Vec<String>
 }
 
 impl TestWordList {
     fn new() -> Result<TestWordList, Box<dyn Error>> {
-        let raw_wordlist = fs::read_to_string("src/assets/wordlist.json")?;
+        Ok(TestWordList{
+            wordlist: TestWordList::read_json("src/assets/wordlist.json")?,
+            answerlist: TestWordList::read_json("src/assets/answers.json")?
+        })
+    }
+
+    fn read_json(path: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        let raw_wordlist = fs::read_to_string(path)?;
         let json_wordlist: Value = serde_json::from_str(&raw_wordlist)?;
 
         let wordlist: &Vec<Value> = json_wordlist.get("wordlist").expect("Did not find attribute \"wordlist\" in JSON file").as_array().expect("Failed to retrieve array from wordlist");
 
         // Convert &Vec<Value> to Vec<String> via mapping so the actual WordleWords.wordlist can be compared.
-        let wordlist: Vec<String> = wordlist.iter().map(|e| e.as_str().expect("Failed to convert json value to str").to_string()).collect();
- 
-        Ok(TestWordList{ wordlist: wordlist })
+        Ok(wordlist.iter().map(|e| e.as_str().expect("Failed to convert json value to str").to_string()).collect())
     }
 }
 
 #[test]
 fn create_offline_wordlist() -> Result<(), Box<dyn Error>> {
-    let wordle_words = words::WordleWords::new(false)?;
+    let wordle_words = words::WordleWords::new(&source::RandomSource::new(None), 5)?;
     let test_word_list = TestWordList::new()?;
 
     let test_hash: HashSet<String> = test_word_list.wordlist.into_iter().collect();
@@ -62,54 +81,1372 @@ fn create_offline_wordlist() -> Result<(), Box<dyn Error>> {
 
     assert_eq!(sym_diff.len(), 0);
 
+    let test_answer_hash: HashSet<String> = test_word_list.answerlist.into_iter().collect();
+    let wordle_answers_hash: HashSet<String> = wordle_words.get_answerlist().clone().into_iter().collect();
+    let answer_sym_diff: Vec<&String> = test_answer_hash.symmetric_difference(&wordle_answers_hash).collect();
+
+    assert_eq!(answer_sym_diff.len(), 0);
+    assert!(!wordle_answers_hash.is_empty());
+    assert!(wordle_answers_hash.is_subset(&wordle_words_hash), "Answer pool must be a subset of the guess pool");
+
     Ok(())
 }
 
 #[test]
 fn create_offline_solution() -> Result<(), Box<dyn Error>> {
-    let wordle_words = words::WordleWords::new(false)?;
+    let wordle_words = words::WordleWords::new(&source::RandomSource::new(None), 5)?;
     let test_word_list = TestWordList::new()?;
 
-    assert!(test_word_list.wordlist.contains(&String::from(wordle_words.get_solution())));
+    assert!(test_word_list.answerlist.contains(&String::from(wordle_words.get_solution())));
 
     Ok(())
 }
 
-// TODO: Online word solution tests! Mock reqwest!
+#[test]
+fn validate_guesses_against_both_pools() -> Result<(), Box<dyn Error>> {
+    let wordle_words = words::WordleWords::new(&source::RandomSource::new(None), 5)?;
+
+    // "aalii" is accepted as a guess but is never drawn as an answer.
+    assert!(wordle_words.is_valid_guess("aalii"));
+    assert!(!wordle_words.get_answerlist().contains(&String::from("aalii")));
+    assert!(wordle_words.is_valid_guess("cigar"));
+    assert!(wordle_words.is_valid_guess("CIGAR"));
+    assert!(!wordle_words.is_valid_guess("zzzzz"));
+
+    Ok(())
+}
 
 #[test]
 fn process_guess_list_population() {
     let user_guess = "nouns";
 
-    let mut guess_list: [Vec<Letter>; 6] = [
-        vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)], 
-        vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)],
-        vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)],
-        vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)],
-        vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)],
-        vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)]
-    ];
+    let mut board = Board::new(5, 6);
 
     let solution = "snaps";
-    let solution_chars: Vec<char> = solution.chars().collect();
     let attempt = 3;
     
-    process_guess(user_guess, &mut guess_list, solution, attempt);
+    process_guess(user_guess, &mut board, solution, attempt);
 
-    for (letter, char) in guess_list[usize::from(attempt) - 1].iter().zip(user_guess.chars()) {
+    for (letter, char) in board.rows()[usize::from(attempt) - 1].iter().zip(user_guess.chars()) {
         assert_eq!(letter.value(), char);
     }
 
-    for (letter, char) in guess_list[usize::from(attempt) - 1].iter().zip(solution.chars()) {
-        let status: LetterState;
-        if letter.value() == char {
-            status = LetterState::Correct;
-        } else if solution_chars.contains(&letter.value()) {
-            status = LetterState::Exists;
-        } else {
-            status = LetterState::Incorrect;
+    let expected = [LetterState::Exists, LetterState::Incorrect, LetterState::Incorrect, LetterState::Incorrect, LetterState::Correct];
+
+    for (letter, status) in board.rows()[usize::from(attempt) - 1].iter().zip(expected.iter()) {
+        assert_eq!(letter.status(), status);
+    }
+}
+
+#[test]
+fn score_guess_duplicate_letters() {
+    use LetterState::{Correct, Exists, Incorrect};
+
+    // Two S's in the solution: one matched in place, one left over for a single yellow.
+    assert_eq!(score_guess("sassy", "snaps"), vec![Correct, Exists, Exists, Incorrect, Incorrect]);
+    assert_eq!(score_guess("speed", "abide"), vec![Incorrect, Incorrect, Exists, Incorrect, Exists]);
+    assert_eq!(score_guess("eerie", "abide"), vec![Incorrect, Incorrect, Incorrect, Exists, Correct]);
+    assert_eq!(score_guess("llama", "hello"), vec![Exists, Exists, Incorrect, Incorrect, Incorrect]);
+    assert_eq!(score_guess("lolly", "hello"), vec![Incorrect, Exists, Correct, Correct, Incorrect]);
+    assert_eq!(score_guess("snaps", "snaps"), vec![Correct; 5]);
+}
+
+// A deliberately different formulation of Wordle scoring to check score_guess against: a
+// misplaced letter is yellow only if the solution has more unmatched copies of it than the
+// guess has already spent on earlier non-green positions.
+fn reference_score(user_guess: &str, solution: &str) -> Vec<LetterState> {
+    let guess: Vec<char> = user_guess.chars().collect();
+    let answer: Vec<char> = solution.chars().collect();
+    let green = |i: usize| guess[i] == answer[i];
+
+    (0..guess.len()).map(|i| {
+        if green(i) {
+            return LetterState::Correct;
+        }
+
+        let available = (0..answer.len()).filter(|&j| answer[j] == guess[i] && !green(j)).count();
+        let spent = (0..i).filter(|&j| guess[j] == guess[i] && !green(j)).count();
+
+        if spent < available { LetterState::Exists } else { LetterState::Incorrect }
+    }).collect()
+}
+
+lazy_static! {
+    static ref BUNDLED_WORDS: Vec<String> = TestWordList::new().expect("Failed to load test wordlist").wordlist;
+}
+
+#[test]
+fn score_guess_whole_wordlist_self_match() {
+    for word in BUNDLED_WORDS.iter() {
+        assert_eq!(score_guess(word, word), vec![LetterState::Correct; 5], "{} should fully match itself", word);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(20_000))]
+
+    #[test]
+    fn score_guess_matches_reference(
+        guess in proptest::sample::select(BUNDLED_WORDS.clone()),
+        solution in proptest::sample::select(BUNDLED_WORDS.clone())
+    ) {
+        prop_assert_eq!(score_guess(&guess, &solution), reference_score(&guess, &solution));
+    }
+
+    #[test]
+    fn score_guess_marks_at_most_solution_count(
+        guess in proptest::sample::select(BUNDLED_WORDS.clone()),
+        solution in proptest::sample::select(BUNDLED_WORDS.clone())
+    ) {
+        let states = score_guess(&guess, &solution);
+
+        for char in guess.chars() {
+            let marked = guess.chars().zip(states.iter()).filter(|(c, s)| *c == char && **s != LetterState::Incorrect).count();
+            let in_guess = guess.chars().filter(|c| *c == char).count();
+            let in_solution = solution.chars().filter(|c| *c == char).count();
+
+            prop_assert_eq!(marked, in_guess.min(in_solution));
+        }
+    }
+}
+
+fn played_rows(guesses: &[&str], solution: &str) -> Vec<Vec<Letter>> {
+    guesses.iter().map(|guess| {
+        guess.chars().zip(score_guess(guess, solution)).map(|(char, status)| Letter::new(char, status)).collect()
+    }).collect()
+}
+
+#[test]
+fn hard_mode_allows_consistent_guesses() {
+    let rows = played_rows(&["crane"], "rebut");
+
+    assert_eq!(check_hard_mode("rebut", &rows), Ok(()));
+    assert_eq!(check_hard_mode("rusty", &rows), Err(game::GuessError::HardMode(HardModeViolation::MissingLetter { letter: 'e', count: 1 })));
+    assert_eq!(check_hard_mode("anything", &[]), Ok(()));
+}
+
+#[test]
+fn hard_mode_enforces_green_positions() {
+    let rows = played_rows(&["track"], "prism");
+
+    assert_eq!(check_hard_mode("shirt", &rows), Err(game::GuessError::HardMode(HardModeViolation::WrongLetter { position: 2, letter: 'r' })));
+    assert_eq!(check_hard_mode("brims", &rows), Ok(()));
+
+    let rows = played_rows(&["lolly"], "hello");
+    assert_eq!(check_hard_mode("hello", &rows), Ok(()));
+    assert_eq!(check_hard_mode("helps", &rows), Err(game::GuessError::HardMode(HardModeViolation::WrongLetter { position: 4, letter: 'l' })));
+}
+
+#[test]
+fn hard_mode_enforces_revealed_letters() {
+    let rows = played_rows(&["about"], "crush");
+
+    assert_eq!(check_hard_mode("shirt", &rows), Err(game::GuessError::HardMode(HardModeViolation::MissingLetter { letter: 'u', count: 1 })));
+    assert_eq!(check_hard_mode("usher", &rows), Ok(()));
+
+    // Two E's were revealed in one row, so a single E no longer satisfies the hint.
+    let rows = played_rows(&["eerie"], "beret");
+    assert_eq!(check_hard_mode("herbs", &rows), Err(game::GuessError::HardMode(HardModeViolation::MissingLetter { letter: 'e', count: 2 })));
+    assert_eq!(check_hard_mode("beret", &rows), Ok(()));
+}
+
+#[test]
+fn hard_mode_ignores_unplayed_rows() {
+    let mut rows = played_rows(&["crane"], "rebut");
+    rows.push(vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)]);
+
+    assert_eq!(check_hard_mode("rebut", &rows), Ok(()));
+}
+
+fn temp_stats_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("rustle-test-{}-{}", std::process::id(), name)).join("stats.json")
+}
+
+#[test]
+fn stats_track_streaks_and_distribution() {
+    let mut stats = stats::Stats::default();
+
+    stats.record_win(3);
+    stats.record_win(4);
+    stats.record_win(3);
+    stats.record_loss();
+    stats.record_win(6);
+
+    assert_eq!(stats.games_played(), 5);
+    assert_eq!(stats.games_won(), 4);
+    assert_eq!(stats.win_percentage(), 80);
+    assert_eq!(stats.current_streak(), 1);
+    assert_eq!(stats.max_streak(), 3);
+    assert_eq!(stats.guess_distribution(), &vec![0, 0, 2, 1, 0, 1]);
+}
+
+#[test]
+fn stats_round_trip_through_disk() -> Result<(), Box<dyn Error>> {
+    let path = temp_stats_path("round-trip");
+
+    assert_eq!(stats::Stats::load(&path)?, stats::Stats::default());
+
+    let mut stats = stats::Stats::default();
+    stats.record_win(2);
+    stats.record_loss();
+    stats.save(&path)?;
+
+    assert_eq!(stats::Stats::load(&path)?, stats);
+
+    fs::remove_dir_all(path.parent().unwrap())?;
+
+    Ok(())
+}
+
+#[test]
+fn stats_reject_newer_versions_and_fill_missing_fields() -> Result<(), Box<dyn Error>> {
+    let path = temp_stats_path("versions");
+    fs::create_dir_all(path.parent().unwrap())?;
+
+    fs::write(&path, format!("{{\"version\": {}}}", stats::STATS_VERSION + 1))?;
+    assert!(stats::Stats::load(&path).is_err());
+
+    fs::write(&path, "{\"games_played\": 2, \"games_won\": 1}")?;
+    let stats = stats::Stats::load(&path)?;
+    assert_eq!(stats.games_played(), 2);
+    assert_eq!(stats.win_percentage(), 50);
+    assert_eq!(stats.guess_distribution().len(), 6);
+
+    fs::remove_dir_all(path.parent().unwrap())?;
+
+    Ok(())
+}
+
+#[test]
+fn puzzle_number_from_date() {
+    let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+    assert_eq!(words::puzzle_number(date(2021, 6, 19)), 0);
+    assert_eq!(words::puzzle_number(date(2021, 6, 20)), 1);
+    assert_eq!(words::puzzle_number(date(2024, 3, 15)), 1000);
+}
+
+#[test]
+fn share_text_formats() {
+    let mut rows = played_rows(&["crane", "lolly", "hello"], "hello");
+    rows.resize_with(6, || vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)]);
+
+    assert_eq!(
+        share::share_text(&rows, &share::share_title(Some(1234), None), false, share::ShareFormat::Emoji),
+        "Rustle 1,234 3/6\n\n⬛⬛⬛⬛🟨\n⬛🟨🟩🟩⬛\n🟩🟩🟩🟩🟩"
+    );
+    assert_eq!(
+        share::share_text(&rows, &share::share_title(Some(99), None), true, share::ShareFormat::HighContrast),
+        "Rustle 99 3/6*\n\n⬛⬛⬛⬛🟦\n⬛🟦🟧🟧⬛\n🟧🟧🟧🟧🟧"
+    );
+    assert_eq!(
+        share::share_text(&rows, &share::share_title(None, None), false, share::ShareFormat::Ascii),
+        "Rustle offline 3/6\n\n....Y\n.YGG.\nGGGGG"
+    );
+}
+
+#[test]
+fn share_text_lost_game() {
+    let rows = played_rows(&["crane", "crane", "crane", "crane", "crane", "crane"], "hello");
+
+    assert!(share::share_text(&rows, &share::share_title(Some(1000000), Some(7)), false, share::ShareFormat::Emoji).starts_with("Rustle 1,000,000 X/6\n"));
+}
+
+#[test]
+fn parse_share_format() {
+    assert_eq!("emoji".parse(), Ok(share::ShareFormat::Emoji));
+    assert_eq!("Contrast".parse(), Ok(share::ShareFormat::HighContrast));
+    assert_eq!("ascii".parse(), Ok(share::ShareFormat::Ascii));
+    assert!("sms".parse::<share::ShareFormat>().is_err());
+}
+
+#[test]
+fn keyboard_keeps_best_letter_state() {
+    // "lolly" then "hello" against "hello": L is yellow then green, O yellow then green.
+    let rows = played_rows(&["crane", "lolly"], "hello");
+    let states = keyboard::key_states(&rows);
+
+    assert_eq!(states.get(&'c'), Some(&LetterState::Incorrect));
+    assert_eq!(states.get(&'e'), Some(&LetterState::Exists));
+    assert_eq!(states.get(&'l'), Some(&LetterState::Correct));
+    assert_eq!(states.get(&'o'), Some(&LetterState::Exists));
+    assert_eq!(states.get(&'y'), Some(&LetterState::Incorrect));
+    assert_eq!(states.get(&'z'), None);
+
+    // A later gray copy of a letter never downgrades an earlier green or yellow.
+    let rows = played_rows(&["hello", "lolly", "eerie"], "hello");
+    let states = keyboard::key_states(&rows);
+
+    assert_eq!(states.get(&'e'), Some(&LetterState::Correct));
+    assert_eq!(states.get(&'l'), Some(&LetterState::Correct));
+}
+
+#[test]
+fn keyboard_rows_follow_qwerty() {
+    let mut rows = played_rows(&["crane"], "rebut");
+    rows.push(vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)]);
+
+    let keyboard_rows = keyboard::keyboard_rows(&rows, &keyboard::QWERTY_ROWS);
+    let layout: Vec<String> = keyboard_rows.iter().map(|row| row.iter().map(|key| key.value()).collect()).collect();
+
+    assert_eq!(layout, vec!["qwertyuiop", "asdfghjkl", "zxcvbnm"]);
+    assert_eq!(keyboard_rows[0][3].status(), &LetterState::Exists); // R
+    assert_eq!(keyboard_rows[1][0].status(), &LetterState::Incorrect); // A
+    assert_eq!(keyboard_rows[2][6].status(), &LetterState::NotExists); // M
+}
+
+#[test]
+fn puzzle_date_from_number() {
+    let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+    assert_eq!(words::puzzle_date(0), Ok(date(2021, 6, 19)));
+    assert_eq!(words::puzzle_date(1000), Ok(date(2024, 3, 15)));
+    assert!(words::puzzle_date(-1).is_err());
+
+    for number in [1, 197, 365, 1234, 2000] {
+        assert_eq!(words::puzzle_number(words::puzzle_date(number).unwrap()), number);
+    }
+}
+
+#[test]
+fn parse_requested_puzzle_date() {
+    assert_eq!(words::parse_puzzle_date("2024-03-15"), Ok(chrono::NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()));
+    assert_eq!(words::parse_puzzle_date("2021-06-19"), Ok(words::first_puzzle_date()));
+    assert!(words::parse_puzzle_date("2021-06-18").is_err());
+    assert!(words::parse_puzzle_date("2023-02-30").is_err());
+    assert!(words::parse_puzzle_date("15/03/2024").is_err());
+}
+
+fn temp_cache(name: &str, refresh: bool) -> cache::SolutionCache {
+    cache::SolutionCache::new(std::env::temp_dir().join(format!("rustle-test-{}-{}", std::process::id(), name)), refresh)
+}
+
+#[test]
+fn solution_cache_round_trip() -> Result<(), Box<dyn Error>> {
+    let solution_cache = temp_cache("cache-round-trip", false);
+    let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let response = words::SolutionResponse {
+        id: 2524,
+        solution: String::from("ember"),
+        print_date: String::from("2024-03-15"),
+        days_since_launch: 1000,
+        editor: Some(String::from("Tracy Bennett"))
+    };
+
+    assert_eq!(solution_cache.get(date), None);
+
+    solution_cache.store(date, &response)?;
+    assert_eq!(solution_cache.get(date), Some(response.clone()));
+    assert_eq!(solution_cache.get(date.succ_opt().unwrap()), None);
+
+    // Refreshing ignores what's on disk.
+    assert_eq!(cache::SolutionCache::new(solution_cache.dir().to_path_buf(), true).get(date), None);
+
+    fs::remove_dir_all(solution_cache.dir())?;
+
+    Ok(())
+}
+
+#[test]
+fn solution_cache_ignores_corrupt_entries() -> Result<(), Box<dyn Error>> {
+    let solution_cache = temp_cache("cache-corrupt", false);
+    let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 16).unwrap();
+
+    fs::create_dir_all(solution_cache.dir())?;
+    fs::write(solution_cache.dir().join("2024-03-16.json"), "{\"not\": \"a solution\"")?;
+    assert_eq!(solution_cache.get(date), None);
+
+    // Only the solution is required, the rest of the NYT fields are optional.
+    fs::write(solution_cache.dir().join("2024-03-16.json"), "{\"solution\": \"smile\"}")?;
+    assert_eq!(solution_cache.get(date).map(|response| response.solution), Some(String::from("smile")));
+
+    fs::remove_dir_all(solution_cache.dir())?;
+
+    Ok(())
+}
+
+// Serves canned NYT responses on a local port: each route is a request path with the status and
+// body to answer it with, anything else gets a 404.
+fn stub_nyt_server(routes: Vec<(&'static str, u16, &'static str)>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind stub server");
+    let base_url = format!("http://{}/svc/wordle/v2", listener.local_addr().expect("Failed to get stub server address"));
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut reader = BufReader::new(stream.try_clone().expect("Failed to clone stub stream"));
+
+            let mut request_line = String::new();
+            if reader.read_line(&mut request_line).is_err() {
+                continue;
+            }
+
+            // Drain the headers, the stub never needs them.
+            let mut header = String::new();
+            while reader.read_line(&mut header).is_ok_and(|read| read > 2) {
+                header.clear();
+            }
+
+            let path = request_line.split_whitespace().nth(1).unwrap_or("");
+            let (status, body) = routes.iter()
+                .find(|(route, _, _)| *route == path)
+                .map_or((404, "{\"status\":\"ERROR\",\"errors\":[\"Not Found\"]}"), |(_, status, body)| (*status, *body));
+
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {} STUB\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+        }
+    });
+
+    base_url
+}
+
+const STUB_SOLUTION_JSON: &str = "{\"id\":2524,\"solution\":\"ember\",\"print_date\":\"2024-03-15\",\"days_since_launch\":1000,\"editor\":\"Tracy Bennett\"}";
+
+#[test]
+fn nyt_source_fetches_from_stub_server() -> Result<(), Box<dyn Error>> {
+    let base_url = stub_nyt_server(vec![("/svc/wordle/v2/2024-03-15.json", 200, STUB_SOLUTION_JSON)]);
+    let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+
+    let solution = source::NytSource::with_base_url(&base_url, date, None).get_solution(&[])?;
+
+    assert_eq!(solution.word, "ember");
+    assert_eq!(solution.puzzle_date, Some(date));
+    assert_eq!(solution.daily_details.as_ref().map(|details| details.days_since_launch), Some(1000));
+    assert_eq!(solution.daily_details.and_then(|details| details.editor), Some(String::from("Tracy Bennett")));
+
+    let wordle_words = words::WordleWords::new(&source::NytSource::with_base_url(&base_url, date, None), 5)?;
+    assert!(!wordle_words.is_offline());
+    assert_eq!(wordle_words.get_puzzle_number(), Some(1000));
+
+    Ok(())
+}
+
+#[test]
+fn nyt_source_reports_missing_and_broken_entries() {
+    let base_url = stub_nyt_server(vec![
+        ("/svc/wordle/v2/2024-03-16.json", 200, "{\"status\":\"OK\"}"),
+        ("/svc/wordle/v2/2024-03-17.json", 500, "")
+    ]);
+    let date = |d| chrono::NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+
+    let missing = source::NytSource::with_base_url(&base_url, date(18), None).get_solution(&[]);
+    assert_eq!(missing, Err(error::RustleError::Network(String::from("The NYT archive has no puzzle for 2024-03-18 (#1003)."))));
+
+    let broken = source::NytSource::with_base_url(&base_url, date(16), None).get_solution(&[]);
+    assert_eq!(broken, Err(error::RustleError::Parse(String::from("Failed to parse remote soltuion JSON."))));
+
+    let server_error = source::NytSource::with_base_url(&base_url, date(17), None).get_solution(&[]);
+    assert_eq!(server_error, Err(error::RustleError::Network(String::from("Failed to retrieve the remote solution."))));
+}
+
+#[test]
+fn nyt_source_serves_cached_solutions_offline() -> Result<(), Box<dyn Error>> {
+    let base_url = stub_nyt_server(vec![("/svc/wordle/v2/2024-03-15.json", 200, STUB_SOLUTION_JSON)]);
+    let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let cache_dir = temp_cache("nyt-source", false).dir().to_path_buf();
+
+    let fetched = source::NytSource::with_base_url(&base_url, date, Some(cache::SolutionCache::new(cache_dir.clone(), false))).get_solution(&[])?;
+
+    // Nothing is listening on port 1, so this can only be answered from the cache.
+    let unreachable = "http://127.0.0.1:1/svc/wordle/v2";
+    let cached = source::NytSource::with_base_url(unreachable, date, Some(cache::SolutionCache::new(cache_dir.clone(), false))).get_solution(&[])?;
+    assert_eq!(cached, fetched);
+
+    let refreshed = source::NytSource::with_base_url(unreachable, date, Some(cache::SolutionCache::new(cache_dir.clone(), true))).get_solution(&[]);
+    assert!(refreshed.is_err());
+
+    fs::remove_dir_all(cache_dir)?;
+
+    Ok(())
+}
+
+#[test]
+fn fallback_chain_uses_first_working_source() -> Result<(), Box<dyn Error>> {
+    let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let chain = source::FallbackChain::new(vec![
+        Box::new(source::NytSource::with_base_url("http://127.0.0.1:1/svc/wordle/v2", date, None)),
+        Box::new(source::FixedSource::new("Crane"))
+    ]);
+
+    let solution = chain.get_solution(&[])?;
+    assert_eq!(solution.word, "crane");
+    assert_eq!(solution.puzzle_date, None);
+    assert_eq!(solution.fallback_errors, vec![String::from("Failed to retrieve the remote solution. Using a fixed solution instead.")]);
+
+    let wordle_words = words::WordleWords::new(&chain, 5)?;
+    assert!(wordle_words.is_offline());
+    assert_eq!(wordle_words.get_fallback_errors(), solution.fallback_errors.as_slice());
+    assert_eq!(wordle_words.get_solution(), "crane");
+
+    let failing = source::FallbackChain::new(vec![
+        Box::new(source::FileSource::new(std::path::PathBuf::from("/nonexistent/rustle/solution.txt"))),
+        Box::new(source::RandomSource::new(None))
+    ]);
+    assert_eq!(failing.get_solution(&[]), Err(error::RustleError::Wordlist(String::from("Failed to retrieve a new local word"))));
+
+    Ok(())
+}
+
+#[test]
+fn local_solution_sources() -> Result<(), Box<dyn Error>> {
+    let answers = vec![String::from("cigar"), String::from("rebut")];
+    assert!(answers.contains(&source::RandomSource::new(None).get_solution(&answers)?.word));
+
+    let dir = std::env::temp_dir().join(format!("rustle-test-{}-file-source", std::process::id()));
+    fs::create_dir_all(&dir)?;
+
+    fs::write(dir.join("plain.txt"), "\n  Humph \nother\n")?;
+    assert_eq!(source::FileSource::new(dir.join("plain.txt")).get_solution(&answers)?.word, "humph");
+
+    fs::write(dir.join("daily.json"), STUB_SOLUTION_JSON)?;
+    assert_eq!(source::FileSource::new(dir.join("daily.json")).get_solution(&answers)?.word, "ember");
+
+    fs::write(dir.join("empty.txt"), "\n\n")?;
+    assert!(source::FileSource::new(dir.join("empty.txt")).get_solution(&answers).is_err());
+
+    fs::remove_dir_all(dir)?;
+
+    Ok(())
+}
+
+#[test]
+fn seeded_solutions_are_reproducible() -> Result<(), Box<dyn Error>> {
+    let first = words::WordleWords::new(&source::RandomSource::new(Some(4821)), 5)?;
+    let second = words::WordleWords::new(&source::RandomSource::new(Some(4821)), 5)?;
+
+    assert_eq!(first.get_solution(), second.get_solution());
+    assert_eq!(first.get_seed(), Some(4821));
+    assert!(first.get_answerlist().contains(first.get_solution()));
+
+    // Unseeded games still get a seed, so they can be replayed too.
+    let unseeded = words::WordleWords::new(&source::RandomSource::new(None), 5)?;
+    let replayed = words::WordleWords::new(&source::RandomSource::new(unseeded.get_seed()), 5)?;
+    assert_eq!(unseeded.get_solution(), replayed.get_solution());
+
+    Ok(())
+}
+
+// These words are what players have been sent as challenges, so they must never change. If this
+// fails, either the seed mapping or the bundled answer list changed.
+#[test]
+fn seeded_solutions_golden() {
+    let answerlist = TestWordList::new().expect("Failed to load test wordlist").answerlist;
+
+    let golden: [(u64, &str); 6] = [
+        (0, "hunch"),
+        (1, "sauna"),
+        (42, "salsa"),
+        (4821, "surly"),
+        (99999, "rouge"),
+        (u64::MAX, "vivid")
+    ];
+
+    for (seed, word) in golden {
+        assert_eq!(source::seeded_solution(seed, &answerlist).map(String::as_str), Some(word), "seed {}", seed);
+    }
+
+    assert_eq!(source::seeded_solution(4821, &[]), None);
+}
+
+#[test]
+fn share_title_variants() {
+    assert_eq!(share::share_title(Some(1234), None), "Rustle 1,234");
+    assert_eq!(share::share_title(None, Some(4821)), "Rustle seed 4821");
+    assert_eq!(share::share_title(None, None), "Rustle offline");
+}
+
+#[test]
+fn board_sized_at_runtime() {
+    let board = Board::new(7, 9);
+
+    assert_eq!(board.word_length(), 7);
+    assert_eq!(board.max_tries(), 9);
+    assert!(board.rows().iter().all(|row| row.len() == 7 && row.iter().all(|letter| letter.status() == &LetterState::NotExists)));
+
+    let mut board = Board::new(4, 3);
+    process_guess("tide", &mut board, "tied", 2);
+    assert_eq!(board.rows()[1].iter().map(|letter| *letter.status()).collect::<Vec<LetterState>>(), vec![LetterState::Correct, LetterState::Correct, LetterState::Exists, LetterState::Exists]);
+}
+
+#[test]
+fn wordlists_for_every_length() -> Result<(), Box<dyn Error>> {
+    for word_length in words::MIN_WORD_LENGTH..=words::MAX_WORD_LENGTH {
+        let wordle_words = words::WordleWords::new(&source::RandomSource::new(Some(4821)), word_length)?;
+
+        assert_eq!(wordle_words.get_word_length(), word_length);
+        assert_eq!(wordle_words.get_solution().len(), word_length);
+        assert!(wordle_words.get_wordlist().iter().all(|word| word.len() == word_length && word.chars().all(|c| c.is_ascii_lowercase())));
+        assert!(!wordle_words.get_answerlist().is_empty());
+        assert!(wordle_words.get_answerlist().iter().all(|word| wordle_words.get_wordlist().contains(word)), "{} letter answers must be valid guesses", word_length);
+    }
+
+    assert!(words::WordleWords::new(&source::RandomSource::new(None), 3).is_err());
+    assert!(words::WordleWords::new(&source::RandomSource::new(None), 9).is_err());
+    assert!(words::WordleWords::new(&source::FixedSource::new("crane"), 6).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn validate_guess_uses_word_length() -> Result<(), Box<dyn Error>> {
+    let game = game::Game::new(words::WordleWords::new(&source::FixedSource::new("planet"), 6)?, DEFAULT_TRIES, false);
+
+    assert_eq!(game.validate("planet"), Ok(()));
+    assert_eq!(game.validate("plane"), Err(game::GuessError::WrongLength { expected: 6, actual: 5 }));
+    assert_eq!(game.validate("planets"), Err(game::GuessError::WrongLength { expected: 6, actual: 7 }));
+    assert_eq!(game.validate("zzzzzz"), Err(game::GuessError::InvalidWord(String::from("zzzzzz"))));
+    assert_eq!(game.validate("plane").unwrap_err().to_string(), "Not enough letters");
+
+    Ok(())
+}
+
+#[test]
+fn game_played_to_a_win() -> Result<(), Box<dyn Error>> {
+    let mut game = game::Game::new(words::WordleWords::new(&source::FixedSource::new("snaps"), 5)?, DEFAULT_TRIES, false);
+
+    let row = game.submit("nouns")?;
+    assert_eq!(row.attempt, 1);
+    assert_eq!(row.states, vec![LetterState::Exists, LetterState::Incorrect, LetterState::Incorrect, LetterState::Incorrect, LetterState::Correct]);
+    assert_eq!(row.outcome, game::Outcome::InProgress);
+
+    // Rejected guesses don't use up an attempt.
+    assert_eq!(game.submit("zzzzz"), Err(game::GuessError::InvalidWord(String::from("zzzzz"))));
+    assert_eq!(game.attempts(), 1);
+
+    let row = game.submit("SNAPS")?;
+    assert_eq!(row.guess, "snaps");
+    assert_eq!(row.outcome, game::Outcome::Won(2));
+    assert_eq!(game.outcome(), game::Outcome::Won(2));
+    assert_eq!(game.rows()[1].iter().map(|letter| letter.value()).collect::<String>(), "snaps");
+    assert_eq!(game.submit("crane"), Err(game::GuessError::GameOver));
+
+    Ok(())
+}
+
+#[test]
+fn game_played_to_a_loss() -> Result<(), Box<dyn Error>> {
+    let mut game = game::Game::new(words::WordleWords::new(&source::FixedSource::new("snaps"), 5)?, 3, false);
+
+    assert_eq!(game.submit("crane")?.outcome, game::Outcome::InProgress);
+    assert_eq!(game.submit("pious")?.outcome, game::Outcome::InProgress);
+    assert_eq!(game.submit("tower")?.outcome, game::Outcome::Lost);
+    assert!(game.is_over());
+    assert_eq!(game.submit("snaps"), Err(game::GuessError::GameOver));
+
+    Ok(())
+}
+
+#[test]
+fn game_enforces_hard_mode() -> Result<(), Box<dyn Error>> {
+    let mut game = game::Game::new(words::WordleWords::new(&source::FixedSource::new("cigar"), 5)?, DEFAULT_TRIES, true);
+
+    game.submit("crane")?;
+
+    assert_eq!(game.submit("tower"), Err(game::GuessError::HardMode(HardModeViolation::WrongLetter { position: 1, letter: 'c' })));
+    assert_eq!(game.submit("tower").unwrap_err().to_string(), "Hard mode: 1st letter must be C");
+    assert_eq!(game.submit("cigar")?.outcome, game::Outcome::Won(2));
+
+    Ok(())
+}
+
+#[test]
+fn errors_are_classified() -> Result<(), Box<dyn Error>> {
+    assert!(matches!(words::parse_puzzle_date("15/03/2024"), Err(error::RustleError::Parse(_))));
+    assert!(matches!("sms".parse::<share::ShareFormat>(), Err(error::RustleError::Parse(_))));
+    assert!(matches!(words::WordleWords::new(&source::RandomSource::new(None), 9), Err(error::RustleError::Wordlist(_))));
+    assert!(matches!(source::FileSource::new(std::env::temp_dir().join("rustle-missing-solution.txt")).get_solution(&[]), Err(error::RustleError::Storage(_))));
+
+    let path = temp_stats_path("corrupt");
+    fs::create_dir_all(path.parent().expect("Stats path has no parent"))?;
+    fs::write(&path, "{ not json")?;
+    assert!(matches!(stats::Stats::load(&path), Err(error::RustleError::Parse(_))));
+    fs::remove_dir_all(path.parent().unwrap())?;
+
+    // Errors read the same as the message they carry, so they can be shown as is.
+    let invalid_guess = error::RustleError::from(game::GuessError::InvalidWord(String::from("zzzzz")));
+    assert_eq!(invalid_guess.to_string(), "Invalid word \"zzzzz\"");
+    assert!(invalid_guess.source().is_some());
+
+    Ok(())
+}
+
+#[test]
+fn saved_games_resume_where_they_left_off() -> Result<(), Box<dyn Error>> {
+    let path = temp_stats_path("saved-game").with_file_name("seed-4821-5.json");
+    let words_for = |seed| words::WordleWords::new(&source::RandomSource::new(Some(seed)), 5);
+
+    assert_eq!(save::SavedGame::load(&path)?, None);
+
+    let mut game = game::Game::new(words_for(4821)?, 4, true);
+    assert_eq!(save::SavedGame::puzzle_key(game.words()), Some(String::from("seed-4821-5")));
+
+    let first_guess = if game.solution() == "crane" { "slate" } else { "crane" };
+    game.submit(first_guess)?;
+    save::SavedGame::from_game("seed-4821-5", &game).save(&path)?;
+
+    let saved = save::SavedGame::load(&path)?.expect("Saved game was not written");
+    assert_eq!(saved.guesses(), &[String::from(first_guess)]);
+    assert!(saved.matches(game.solution()));
+    assert!(!fs::read_to_string(&path)?.contains(game.solution()), "Saved games must not spoil the solution");
+
+    let mut resumed = game::Game::new(words_for(4821)?, saved.max_tries(), saved.hard_mode());
+    saved.replay(&mut resumed)?;
+    assert_eq!(resumed.attempts(), 1);
+    assert_eq!(resumed.max_tries(), 4);
+    assert!(resumed.hard_mode());
+    assert_eq!(resumed.guesses(), game.guesses());
+
+    assert!(saved.check_settings(4, true).is_ok());
+    for (max_tries, hard_mode) in [(DEFAULT_TRIES, true), (4, false)] {
+        let Err(error::RustleError::Storage(message)) = saved.check_settings(max_tries, hard_mode) else { panic!("mismatched settings weren't turned away") };
+        assert!(message.contains("4 tries in hard mode") && message.contains("--tries 4 --hard=true") && message.contains("--new"), "{}", message);
+    }
+
+    let newer = fs::read_to_string(&path)?.replace(&format!("\"version\": {}", save::SAVE_VERSION), &format!("\"version\": {}", save::SAVE_VERSION + 1));
+    fs::write(&path, newer)?;
+    assert!(matches!(save::SavedGame::load(&path), Err(error::RustleError::Storage(_))));
+
+    save::SavedGame::discard(&path)?;
+    save::SavedGame::discard(&path)?;
+    assert_eq!(save::SavedGame::load(&path)?, None);
+
+    fs::remove_dir_all(path.parent().unwrap())?;
+
+    Ok(())
+}
+
+#[test]
+fn saved_games_are_keyed_by_puzzle() -> Result<(), Box<dyn Error>> {
+    let base_url = stub_nyt_server(vec![("/svc/wordle/v2/2024-03-15.json", 200, STUB_SOLUTION_JSON)]);
+    let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let daily = words::WordleWords::new(&source::NytSource::with_base_url(&base_url, date, None), 5)?;
+
+    assert_eq!(save::SavedGame::puzzle_key(&daily), Some(String::from("daily-2024-03-15")));
+    assert_eq!(save::SavedGame::puzzle_key(&words::WordleWords::new(&source::RandomSource::new(Some(7)), 6)?), Some(String::from("seed-7-6")));
+    assert_eq!(save::SavedGame::puzzle_key(&words::WordleWords::new(&source::FixedSource::new("crane"), 5)?), None);
+
+    // The hash has to stay the same across releases for older saves to keep working.
+    assert_eq!(save::solution_hash(""), 0xCBF2_9CE4_8422_2325);
+    assert_eq!(save::solution_hash("a"), 0xAF63_DC4C_8601_EC8C);
+    assert_ne!(save::solution_hash("crane"), save::solution_hash("slate"));
+
+    Ok(())
+}
+
+lazy_static! {
+    // Every bundled guess list: English at every length, and the other languages' five letter packs.
+    static ref BUNDLED_WORD_PACKS: Vec<Vec<String>> = lang::Language::ALL.iter()
+        .flat_map(|language| (words::MIN_WORD_LENGTH..=words::MAX_WORD_LENGTH).filter_map(|length| words::load_bundled_words(*language, length).ok()))
+        .map(|(wordlist, _)| wordlist)
+        .collect();
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(5_000))]
+
+    #[test]
+    fn solver_pattern_matches_score_guess(
+        pack in 0..BUNDLED_WORD_PACKS.len(),
+        guess in any::<proptest::sample::Index>(),
+        solution in any::<proptest::sample::Index>()
+    ) {
+        let pack = &BUNDLED_WORD_PACKS[pack];
+        let (guess, solution) = (guess.get(pack), solution.get(pack));
+
+        prop_assert_eq!(solver::feedback_pattern(guess, solution), solver::states_pattern(&score_guess(guess, solution)));
+    }
+}
+
+// The solver's fast feedback patterns have to agree with score_guess everywhere, so check every
+// pair of words made from a few letters of each alphabet (its last ones, so accented letters, ñ and
+// ß are included), which covers every way letters can repeat, at every length.
+#[test]
+fn solver_pattern_matches_score_guess_exhaustively() {
+    fn every_word(letters: &[char], length: usize) -> Vec<String> {
+        (0..length).fold(vec![String::new()], |words, _| {
+            words.iter().flat_map(|word| letters.iter().map(move |letter| format!("{}{}", word, letter))).collect()
+        })
+    }
+
+    // One thread per language and length keeps this quick.
+    thread::scope(|scope| {
+        for language in lang::Language::ALL {
+            for length in words::MIN_WORD_LENGTH..=words::MAX_WORD_LENGTH {
+                scope.spawn(move || {
+                    // Two letters are enough to repeat in every way on the longer words.
+                    let letters: Vec<char> = language.alphabet().chars().rev().take(if length <= 5 { 3 } else { 2 }).collect();
+                    let words = every_word(&letters, length);
+
+                    for guess in &words {
+                        for solution in &words {
+                            assert_eq!(solver::feedback_pattern(guess, solution), solver::states_pattern(&score_guess(guess, solution)), "{} against {}", guess, solution);
+                        }
+                    }
+                });
+            }
+        }
+    });
+}
+
+#[test]
+fn solver_narrows_candidates_and_suggests_guesses() -> Result<(), Box<dyn Error>> {
+    let mut game = game::Game::new(words::WordleWords::new(&source::FixedSource::new("cigar"), 5)?, DEFAULT_TRIES, false);
+    game.submit("slate")?;
+
+    let solver = solver::Solver::for_game(&game);
+    let answers = game.words().get_answerlist().len();
+
+    assert!(solver.candidates().len() < answers);
+    assert!(solver.candidates().contains(&"cigar"));
+    assert!(solver.candidates().iter().all(|word| score_guess("slate", word) == score_guess("slate", "cigar")));
+
+    let ranked = solver.rank();
+    assert_eq!(ranked.len(), game.words().get_wordlist().len());
+    assert!(ranked.windows(2).all(|pair| pair[0].entropy >= pair[1].entropy - 1e-9));
+
+    let hint = solver.hint().expect("No hint was given");
+    assert_eq!(hint.word, ranked[0].word);
+    assert_eq!(hint.remaining, solver.candidates().len());
+    assert!(hint.entropy > 0.0 && hint.entropy <= (hint.remaining as f64).log2());
+
+    Ok(())
+}
+
+#[test]
+fn solver_hints_follow_hard_mode_and_small_pools() -> Result<(), Box<dyn Error>> {
+    let mut game = game::Game::new(words::WordleWords::new(&source::FixedSource::new("cigar"), 5)?, DEFAULT_TRIES, true);
+    game.submit("crane")?;
+
+    let rows = game.rows();
+    assert!(solver::Solver::for_game(&game).rank().iter().all(|ranked| check_hard_mode(&ranked.word, rows).is_ok()));
+
+    // Answers outside the answer pool are still found from the full list of guesses.
+    let guesses: Vec<String> = ["cigar", "rebut", "sissy", "humph", "awake"].iter().map(|word| word.to_string()).collect();
+    let answers: Vec<String> = ["rebut", "sissy"].iter().map(|word| word.to_string()).collect();
+    let mut solver = solver::Solver::with_words(&guesses, &answers, false);
+
+    assert_eq!(solver.hint().map(|hint| (hint.word, hint.remaining)), Some((String::from("rebut"), 2)));
+
+    solver.apply("humph", &score_guess("humph", "cigar"));
+    assert_eq!(solver.candidates(), &["sissy"]);
+
+    solver.apply("rebut", &score_guess("rebut", "cigar"));
+    assert_eq!(solver.candidates(), &["cigar"]);
+
+    let hint = solver.hint().expect("No hint was given");
+    assert_eq!(hint.describe(lang::Language::English), "It can only be CIGAR");
+
+    let german = solver::Hint { word: String::from("größe"), entropy: 0.0, remaining: 1 };
+    assert_eq!(german.describe(lang::Language::German), "Es kann nur GRÖẞE sein");
+
+    Ok(())
+}
+
+#[test]
+fn analysis_reviews_each_row() {
+    let guesses: Vec<String> = ["cigar", "rebut", "sissy", "humph", "awake", "blush"].iter().map(|word| word.to_string()).collect();
+    let answers: Vec<String> = ["cigar", "rebut", "sissy", "humph", "awake"].iter().map(|word| word.to_string()).collect();
+    let solver = solver::Solver::with_words(&guesses, &answers, false);
+
+    let analysis = solver::analyze_rows(solver, &played_rows(&["blush", "humph"], "humph"));
+
+    assert_eq!(analysis.len(), 2);
+    assert_eq!(analysis[0].guess, "blush");
+    assert_eq!(analysis[0].states, score_guess("blush", "humph"));
+    assert_eq!((analysis[0].before, analysis[0].after), (5, 1));
+    assert!((analysis[0].bits - 5f64.log2()).abs() < 1e-9);
+    assert!(analysis[0].best.is_some());
+    assert_eq!((analysis[1].before, analysis[1].after, analysis[1].bits), (1, 1, 0.0));
+    assert_eq!(analysis[1].best.as_ref().map(|best| best.word.as_str()), Some("humph"));
+}
+
+#[test]
+fn bench_plays_every_answer() {
+    let guesses: Vec<String> = ["cigar", "rebut", "sissy", "humph", "awake", "blush", "focal", "evade"].iter().map(|word| word.to_string()).collect();
+    let answers: Vec<String> = ["cigar", "rebut", "sissy", "humph", "awake", "blush"].iter().map(|word| word.to_string()).collect();
+
+    let strategies: Vec<Box<dyn solver::Strategy>> = vec![
+        Box::new(solver::GreedyEntropy),
+        Box::new(solver::Minimax),
+        Box::new(solver::FixedOpener { opener: String::from("focal"), then: Box::new(solver::GreedyEntropy) })
+    ];
+
+    for strategy in strategies {
+        let report = bench::run_bench(&guesses, &answers, strategy.as_ref(), 6, false, 3);
+
+        assert_eq!(report.games, answers.len(), "{}", report.strategy);
+        assert_eq!(report.distribution.iter().sum::<u32>() as usize, answers.len());
+        assert_eq!(report.failures, 0);
+        assert!(report.unsolved.is_empty());
+        assert!(report.worst_case >= 1 && report.worst_case <= 6);
+        assert!(report.average_guesses >= 1.0 && report.average_guesses <= report.worst_case as f64);
+    }
+
+    // Opening with a word that can't be the answer means nothing is solved in one.
+    let report = bench::run_bench(&guesses, &answers, &solver::FixedOpener { opener: String::from("evade"), then: Box::new(solver::Minimax) }, 2, false, 1);
+    assert_eq!(report.strategy, "evade opener, then minimax");
+    assert_eq!(report.distribution[0], 0);
+    assert_eq!(report.failures, answers.len() - report.distribution[1] as usize);
+}
+
+#[test]
+fn word_packs_for_other_languages() -> Result<(), Box<dyn Error>> {
+    for language in lang::Language::ALL {
+        let (wordlist, answerlist) = words::load_bundled_words(language, 5)?;
+        let unique: HashSet<&String> = wordlist.iter().collect();
+
+        assert_eq!(unique.len(), wordlist.len(), "{} has duplicate guesses", language.name());
+        assert!(answerlist.len() > 100, "{} has too few answers", language.name());
+
+        for word in wordlist.iter().chain(answerlist.iter()) {
+            assert_eq!(word.chars().count(), 5, "{} word \"{}\"", language.name(), word);
+            assert!(word.chars().all(|letter| language.is_letter(letter)), "{} word \"{}\"", language.name(), word);
         }
 
-        assert_eq!(letter.status(), &status);
+        let keys: String = language.keyboard().concat();
+        assert!(language.alphabet().chars().all(|letter| keys.contains(letter)), "{} keyboard is missing letters", language.name());
+    }
+
+    assert!(matches!(words::load_bundled_words(lang::Language::German, 6), Err(error::RustleError::Wordlist(_))));
+
+    Ok(())
+}
+
+#[test]
+fn games_in_other_languages_accept_accented_letters() -> Result<(), Box<dyn Error>> {
+    let spanish = words::WordleWords::with_language(&source::FixedSource::new("señor"), lang::Language::Spanish, 5)?;
+    let mut game = game::Game::new(spanish, DEFAULT_TRIES, false);
+
+    assert_eq!(game.validate("crane"), Err(game::GuessError::InvalidWord(String::from("crane"))));
+    assert_eq!(game.validate("niño"), Err(game::GuessError::WrongLength { expected: 5, actual: 4 }));
+
+    let row = game.submit("NIÑEZ")?;
+    assert_eq!(row.guess, "niñez");
+    assert_eq!(row.states, vec![LetterState::Incorrect, LetterState::Incorrect, LetterState::Correct, LetterState::Exists, LetterState::Incorrect]);
+
+    assert_eq!(game.submit("señor")?.outcome, game::Outcome::Won(2));
+
+    // ß is its own letter, and is drawn as a capital ẞ.
+    let german = words::WordleWords::with_language(&source::FixedSource::new("größe"), lang::Language::German, 5)?;
+    let game = game::Game::new(german, DEFAULT_TRIES, false);
+
+    assert_eq!(game.validate("grüße"), Ok(()));
+    assert_eq!(lang::to_uppercase_word("größe"), "GRÖẞE");
+    assert_eq!(lang::to_lowercase('Ñ'), 'ñ');
+    assert!(!lang::Language::English.is_letter('é'));
+
+    assert_eq!(save::SavedGame::puzzle_key(&words::WordleWords::with_language(&source::RandomSource::new(Some(7)), lang::Language::French, 5)?), Some(String::from("seed-7-5-fr")));
+
+    Ok(())
+}
+
+#[test]
+fn languages_parse_and_translate_prompts() {
+    assert_eq!("de".parse::<lang::Language>(), Ok(lang::Language::German));
+    assert_eq!("FR".parse::<lang::Language>(), Ok(lang::Language::French));
+    assert_eq!("xx".parse::<lang::Language>(), Err(error::RustleError::Parse(String::from("Unknown language \"xx\", expected one of: en, es, de, fr"))));
+
+    let too_short = game::GuessError::WrongLength { expected: 5, actual: 3 };
+
+    assert_eq!(lang::Language::English.guess_error(&too_short), too_short.to_string());
+    assert_eq!(lang::Language::Spanish.guess_error(&too_short), "Faltan letras");
+    assert_eq!(lang::Language::German.retry_prompt(&game::GuessError::InvalidWord(String::from("abcde"))), "Ungültiges Wort \"abcde\"! Bitte gib ein neues Wort ein:");
+    assert_eq!(lang::Language::French.out_of_tries(6), "Pas trouvé en 6 essais !");
+
+    let moved = game::GuessError::HardMode(HardModeViolation::WrongLetter { position: 2, letter: 'r' });
+    let missing = game::GuessError::HardMode(HardModeViolation::MissingLetter { letter: 'e', count: 2 });
+
+    assert_eq!(moved.to_string(), "Hard mode: 2nd letter must be R");
+    assert_eq!(missing.to_string(), "Hard mode: guess must contain 2 E's");
+    assert_eq!(lang::Language::German.guess_error(&moved), "Schwerer Modus: der 2. Buchstabe muss R sein");
+    assert_eq!(lang::Language::French.guess_error(&missing), "Mode difficile : le mot doit contenir 2 E");
+    assert_eq!(lang::Language::Spanish.guess_error(&game::GuessError::HardMode(HardModeViolation::MissingLetter { letter: 'ñ', count: 1 })), "Modo difícil: la palabra debe contener Ñ");
+    assert_eq!(lang::Language::Spanish.already_played("\"SEÑOR\""), "¡Ya jugaste esta partida! La palabra era \"SEÑOR\", usa --new para jugarla otra vez.");
+
+    let hint = solver::Hint { word: String::from("señor"), entropy: 5.4321, remaining: 42 };
+    assert_eq!(hint.describe(lang::Language::English), "Try SEÑOR: 5.43 bits expected, 42 possible answers left");
+    assert_eq!(hint.describe(lang::Language::Spanish), "Prueba SEÑOR: 5.43 bits esperados, quedan 42 respuestas posibles");
+}
+
+#[test]
+fn statistics_and_analysis_are_translated() -> Result<(), Box<dyn Error>> {
+    let words = words::WordleWords::new(&source::FixedSource::new("cigar"), 5)?;
+    let mut game = game::Game::new(words, DEFAULT_TRIES, false);
+    game.submit("crane")?;
+    game.submit("cigar")?;
+
+    let mut stats = stats::Stats::default();
+    stats.record_win(2);
+
+    let mut english = display::PlainDisplay::new(Vec::new(), lang::Language::English);
+    english.draw_stats(&stats, Some(2))?;
+    let english = String::from_utf8(english.into_inner())?;
+    assert!(english.contains("  Played   Win %    Current Streak    Max Streak\n       1     100                 1             1\n"), "{}", english);
+
+    let mut french = display::PlainDisplay::new(Vec::new(), lang::Language::French);
+    french.draw_stats(&stats, Some(2))?;
+    french.draw_analysis(&solver::analyze(&game))?;
+    let french = String::from_utf8(french.into_inner())?;
+    assert!(french.contains("STATISTIQUES") && french.contains("RÉPARTITION DES ESSAIS") && french.contains("ANALYSE"), "{}", french);
+    assert!(!french.contains("Played") && !french.contains("Best guess"), "{}", french);
+
+    // Every number still ends under the end of its heading, however long the headings are.
+    let lines: Vec<&str> = french.lines().collect();
+    let headings = lines.iter().position(|line| line.contains("Parties")).expect("No stats headings");
+    let heading_ends: Vec<usize> = lang::Language::French.stats_headings().iter()
+        .map(|heading| {
+            let start = lines[headings].find(heading).unwrap();
+            lines[headings][..start].chars().count() + heading.chars().count()
+        })
+        .collect();
+    let value_ends: Vec<usize> = lines[headings + 1].match_indices(char::is_numeric)
+        .filter(|(index, _)| !lines[headings + 1][index + 1..].starts_with(char::is_numeric))
+        .map(|(index, _)| index + 1)
+        .collect();
+    assert_eq!(heading_ends, value_ends);
+
+    Ok(())
+}
+
+#[test]
+fn custom_wordlists_report_every_problem() {
+    let english = lang::Language::English;
+    let path = std::path::Path::new("team.txt");
+    let wordlist_err = |message: &str| Err(error::RustleError::Wordlist(message.to_string()));
+
+    assert_eq!(
+        words::parse_wordlist("# Team names\nAlpha\nbravo\n\ndelta\n", path, english, 5),
+        Ok(vec![String::from("alpha"), String::from("bravo"), String::from("delta")])
+    );
+    assert_eq!(
+        words::parse_wordlist("alpha\nab\nbravo\nalpha\nfoo-b\n", path, english, 5),
+        wordlist_err("The word list \"team.txt\" has 3 problems:\n  line 2: \"ab\" is 2 letters long, expected 5\n  line 4: \"alpha\" is a duplicate of line 1\n  line 5: \"foo-b\" has characters that aren't English letters")
+    );
+
+    // JSON lists are checked the same way, with the line each entry is on.
+    let raw_json = "{\n    \"wordlist\": [\n        \"alpha\",\n        \"alpha\",\n        \"señor\"\n    ]\n}\n";
+    assert_eq!(
+        words::parse_wordlist(raw_json, path, english, 5),
+        wordlist_err("The word list \"team.txt\" has 2 problems:\n  line 4: \"alpha\" is a duplicate of line 3\n  line 5: \"señor\" has characters that aren't English letters")
+    );
+    assert_eq!(words::parse_wordlist(raw_json.replace("\"alpha\",\n", "").as_str(), path, lang::Language::Spanish, 5), Ok(vec![String::from("señor")]));
+
+    let Err(error::RustleError::Wordlist(invalid)) = words::parse_wordlist("{\n  \"words\": []\n}", path, english, 5) else { panic!("a list without \"wordlist\" was accepted") };
+    assert!(invalid.starts_with("The word list \"team.txt\" isn't a valid word list: missing field `wordlist` at line 3"), "{}", invalid);
+    assert_eq!(words::parse_wordlist("# Nothing yet\n", path, english, 5), wordlist_err("The word list \"team.txt\" doesn't have any words in it."));
+}
+
+#[test]
+fn custom_wordlists_load_from_disk() -> Result<(), Box<dyn Error>> {
+    let path = std::env::temp_dir().join(format!("rustle-custom-{}.txt", std::process::id()));
+    fs::write(&path, "alpha\nbravo\ndelta\n")?;
+
+    let answers = words::load_wordlist_file(&path, lang::Language::English, 5)?;
+    let wordle_words = words::WordleWords::with_lists(&source::RandomSource::new(Some(7)), lang::Language::English, 5, answers.clone(), answers.clone())?;
+
+    assert!(answers.contains(wordle_words.get_solution()));
+    assert!(wordle_words.has_custom_lists());
+    assert!(save::SavedGame::puzzle_key(&wordle_words).is_some_and(|key| key.starts_with("seed-7-5-custom-")));
+
+    fs::write(&path, "alpha\nbravo\n")?;
+    let edited = words::WordleWords::with_lists(&source::RandomSource::new(Some(7)), lang::Language::English, 5, answers.clone(), words::load_wordlist_file(&path, lang::Language::English, 5)?)?;
+    assert_ne!(save::SavedGame::puzzle_key(&edited), save::SavedGame::puzzle_key(&wordle_words));
+
+    fs::remove_file(&path)?;
+    assert!(matches!(words::load_wordlist_file(&path, lang::Language::English, 5), Err(error::RustleError::Wordlist(_))));
+
+    Ok(())
+}
+
+fn parse_play(args: &[&str]) -> cli::PlayArgs {
+    match cli::parse_args(std::iter::once("rustle").chain(args.iter().copied())) {
+        Ok(cli::Command::Play(play)) => play,
+        other => panic!("{:?} didn't parse as a game: {:?}", args, other)
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn cli_parses_commands_and_options() {
+    use clap::CommandFactory;
+    use clap::error::ErrorKind;
+
+    cli::Cli::command().debug_assert();
+
+    let defaults = parse_play(&[]);
+    assert_eq!(defaults, parse_play(&["play"]));
+    assert_eq!((defaults.tries, defaults.hard, defaults.words.length, defaults.words.lang), (DEFAULT_TRIES, false, 5, lang::Language::English));
+    assert_eq!(defaults.share_format, share::ShareFormat::Emoji);
+
+    let play = parse_play(&["--hard", "--tries", "8", "--lang=de", "--share-format", "ascii", "--seed", "4821"]);
+    assert!(play.hard);
+    assert_eq!((play.tries, play.words.lang, play.share_format, play.puzzle.seed), (8, lang::Language::German, share::ShareFormat::Ascii, Some(4821)));
+
+    let today = chrono::NaiveDate::from_ymd_opt(2024, 3, 16).unwrap();
+    let march_15 = chrono::NaiveDate::from_ymd_opt(2024, 3, 15);
+    assert_eq!(parse_play(&["--date", "2024-03-15"]).puzzle.requested_date(today), march_15);
+    assert_eq!(parse_play(&["--puzzle", "1000"]).puzzle.requested_date(today), Some(words::puzzle_date(1000).unwrap()));
+    assert_eq!(parse_play(&["--yesterday"]).puzzle.requested_date(today), march_15);
+    assert_eq!(defaults.puzzle.requested_date(today), None);
+
+    assert!(matches!(cli::parse_args(["rustle", "stats"]), Ok(cli::Command::Stats(_))));
+    assert!(matches!(cli::parse_args(["rustle", "--color", "never", "--config=work.toml", "stats"]), Ok(cli::Command::Stats(_))));
+    assert_eq!(cli::parse_args_with_config(["rustle", "--color=never", "--hard"], &config::Config::default()).unwrap().color, cli::ColorChoice::Never);
+
+    let Ok(cli::Command::Solve(solve)) = cli::parse_args(["rustle", "solve", "crane:..Y.g", "SLOTH:G....", "--top", "3"]) else { panic!("solve didn't parse") };
+    assert_eq!(solve.top, 3);
+    assert_eq!(solve.rows[0].guess, "crane");
+    assert_eq!(solve.rows[0].states, vec![LetterState::Incorrect, LetterState::Incorrect, LetterState::Exists, LetterState::Incorrect, LetterState::Correct]);
+    assert_eq!(solve.rows[1].guess, "sloth");
+
+    let Ok(cli::Command::Bench(bench)) = cli::parse_args(["rustle", "bench", "--strategy", "minimax", "--opener", "crane", "--threads", "2"]) else { panic!("bench didn't parse") };
+    assert_eq!((bench.strategy, bench.opener.as_deref(), bench.threads), (cli::StrategyName::Minimax, Some("crane"), Some(2)));
+
+    assert!(matches!(cli::parse_args(["rustle", "words", "--solutions"]), Ok(cli::Command::Words(words)) if words.solutions));
+    assert!(matches!(cli::parse_args(["rustle", "prefetch", "7"]), Ok(cli::Command::Prefetch(prefetch)) if prefetch.days == 7));
+    assert!(cli::parse_args(["rustle", "prefetch", "0"]).is_err());
+    assert!(cli::parse_args(["rustle", "--prefetch", "7"]).is_err());
+
+    let error_kind = |args: &[&str]| cli::parse_args(std::iter::once("rustle").chain(args.iter().copied())).unwrap_err().kind();
+    assert_eq!(error_kind(&["--help"]), ErrorKind::DisplayHelp);
+    assert_eq!(error_kind(&["--version"]), ErrorKind::DisplayVersion);
+    assert_eq!(error_kind(&["--bogus"]), ErrorKind::UnknownArgument);
+    assert_eq!(error_kind(&["stats", "--hard"]), ErrorKind::UnknownArgument);
+    assert_eq!(error_kind(&["--tries", "13"]), ErrorKind::ValueValidation);
+    assert_eq!(error_kind(&["--length", "9"]), ErrorKind::ValueValidation);
+    assert_eq!(error_kind(&["--lang", "xx"]), ErrorKind::ValueValidation);
+    assert_eq!(error_kind(&["--date", "2020-01-01"]), ErrorKind::ValueValidation);
+    assert_eq!(error_kind(&["--puzzle", "-5"]), ErrorKind::ValueValidation);
+    assert!(cli::parse_args(["rustle", "--puzzle", "-5"]).unwrap_err().to_string().contains("Puzzle #-5 doesn't exist"));
+    assert_eq!(error_kind(&["--seed", "1", "--date", "2024-03-15"]), ErrorKind::ArgumentConflict);
+    assert_eq!(error_kind(&["--offline", "--yesterday"]), ErrorKind::ArgumentConflict);
+    assert_eq!(error_kind(&["solve", "crane:..Q.."]), ErrorKind::ValueValidation);
+    assert_eq!(error_kind(&["solve", "crane:..Y"]), ErrorKind::ValueValidation);
+
+    // The config file only fills in what the command line leaves out. How the environment fits in
+    // is checked by tests/cli_env.rs, which can change it without upsetting other tests.
+    let config = config::Config { tries: Some(7), hard: Some(true), length: Some(6), offline: Some(true), ..config::Config::default() };
+    let parse_with_config = |args: &[&str]| cli::parse_args_with_config(std::iter::once("rustle").chain(args.iter().copied()), &config).map(|cli| cli.command);
+
+    let Ok(cli::Command::Play(configured)) = parse_with_config(&["--config", "rustle.toml"]) else { panic!("play didn't parse") };
+    assert_eq!((configured.tries, configured.hard, configured.words.length, configured.puzzle.offline), (7, true, 6, true));
+
+    let Ok(cli::Command::Play(overridden)) = parse_with_config(&["--tries=3", "--hard=false", "--length", "5", "--offline=false"]) else { panic!("play didn't parse") };
+    assert_eq!((overridden.tries, overridden.hard, overridden.words.length, overridden.puzzle.offline), (3, false, 5, false));
+
+    // Online or offline is only a default for games.
+    let Ok(cli::Command::Share(shared)) = parse_with_config(&["share"]) else { panic!("share didn't parse") };
+    assert!(!shared.puzzle.offline);
+    assert_eq!(cli::config_arg(&["rustle".into(), "--config=work.toml".into()]), Some(std::path::PathBuf::from("work.toml")));
+}
+
+#[test]
+fn config_files_are_read_and_checked() -> Result<(), Box<dyn Error>> {
+    let path = std::path::Path::new("/home/player/.config/rustle/config.toml");
+
+    let config = config::Config::parse(
+        "# Rustle settings\nmode = \"offline\"\nhard = true\ntheme = \"mono\"\ntries = 8\nlength = 6\nlang = \"de\"\nshare_format = \"contrast\"\nstats_file = \"stats.json\"\ncache_dir = \"/tmp/rustle\"\n",
+        path
+    )?;
+
+    assert_eq!((config.offline, config.hard, config.tries, config.length), (Some(true), Some(true), Some(8), Some(6)));
+    assert_eq!((config.lang, config.share_format, config.theme), (Some(lang::Language::German), Some(share::ShareFormat::HighContrast), Some(theme::Theme::Monochrome)));
+    assert_eq!(config.stats_file.as_deref(), Some(std::path::Path::new("/home/player/.config/rustle/stats.json")));
+    assert_eq!(config.cache_dir.as_deref(), Some(std::path::Path::new("/tmp/rustle")));
+    assert_eq!(config.games_dir, None);
+    assert_eq!(config::Config::parse("", path)?, config::Config::default());
+
+    let error = |raw: &str| config::Config::parse(raw, path).unwrap_err().to_string();
+    let unknown_key = error("hard = true\n\ncolour = \"blue\"\n");
+    assert!(unknown_key.contains("/home/player/.config/rustle/config.toml") && unknown_key.contains("line 3") && unknown_key.contains("colour"), "{}", unknown_key);
+    assert!(error("tries = 8\ntries = 99").contains("line 2"));
+    assert!(error("length = 3").contains("between 4 and 8"));
+    assert!(error("mode = \"sometimes\"").contains("online"));
+    assert!(error("hard = \"yes\"").contains("line 1"));
+
+    assert_eq!(config::Config::load(std::path::Path::new("/nonexistent/rustle.toml"), false)?, config::Config::default());
+    assert!(config::Config::load(std::path::Path::new("/nonexistent/rustle.toml"), true).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn themes_draw_tiles_at_every_color_depth() {
+    use theme::{ColorDepth, Theme};
+
+    assert!(Theme::HighContrast.tile('A', &LetterState::Correct, ColorDepth::Ansi256).contains("\x1b[48;5;209m"));
+    assert!(Theme::HighContrast.tile('A', &LetterState::Exists, ColorDepth::TrueColor).contains("\x1b[48;2;133;192;249m"));
+    assert!(Theme::Light.tile('A', &LetterState::Correct, ColorDepth::TrueColor).contains("\x1b[38;2;255;255;255m"));
+
+    let mono: Vec<String> = [LetterState::Correct, LetterState::Exists, LetterState::Incorrect, LetterState::NotExists].iter()
+        .map(|status| Theme::Monochrome.tile('A', status, ColorDepth::TrueColor))
+        .collect();
+    assert_eq!(mono, vec!["[A]", "(A)", "·A·", " A "]);
+    assert_eq!((Theme::Monochrome.bar(3, true, ColorDepth::Basic), Theme::Monochrome.bar(2, false, ColorDepth::Basic)), (String::from("###"), String::from("==")));
+
+    assert_eq!(ColorDepth::from_env(Some("truecolor"), Some("xterm-256color")), ColorDepth::TrueColor);
+    assert_eq!(ColorDepth::from_env(Some("24bit"), None), ColorDepth::TrueColor);
+    assert_eq!(ColorDepth::from_env(None, Some("xterm-256color")), ColorDepth::Ansi256);
+    assert_eq!(ColorDepth::from_env(None, Some("xterm")), ColorDepth::Basic);
+
+    assert_eq!("high-contrast".parse::<Theme>().unwrap(), Theme::HighContrast);
+    assert_eq!("Monochrome".parse::<Theme>().unwrap(), Theme::Monochrome);
+    assert!("neon".parse::<Theme>().is_err());
+}
+
+#[test]
+fn plain_output_prints_rows_as_text() {
+    use std::ffi::OsStr;
+    use cli::ColorChoice;
+
+    let row = vec![
+        Letter::new('c', LetterState::Correct),
+        Letter::new('r', LetterState::Incorrect),
+        Letter::new('a', LetterState::Exists),
+        Letter::new('n', LetterState::Incorrect),
+        Letter::new('e', LetterState::Incorrect)
+    ];
+    assert_eq!(display::plain_row(&row), "CRANE  G.Y..");
+
+    assert!(ColorChoice::Auto.use_color(true, None));
+    assert!(ColorChoice::Auto.use_color(true, Some(OsStr::new(""))));
+    assert!(!ColorChoice::Auto.use_color(true, Some(OsStr::new("1"))));
+    assert!(!ColorChoice::Auto.use_color(false, None));
+    assert!(ColorChoice::Always.use_color(false, Some(OsStr::new("1"))));
+    assert!(!ColorChoice::Never.use_color(true, None));
+
+    let args = |args: &[&str]| args.iter().map(OsString::from).collect::<Vec<_>>();
+    assert_eq!(cli::early_color_choice(&args(&["rustle", "--color", "never", "stats"]), Some(OsStr::new("always"))), ColorChoice::Never);
+    assert_eq!(cli::early_color_choice(&args(&["rustle", "--color=always"]), None), ColorChoice::Always);
+    assert_eq!(cli::early_color_choice(&args(&["rustle", "--hard"]), Some(OsStr::new("never"))), ColorChoice::Never);
+    assert_eq!(cli::early_color_choice(&args(&["rustle", "--colorful"]), Some(OsStr::new("sometimes"))), ColorChoice::Auto);
+}
+
+#[test]
+fn plain_display_prints_text_without_escape_codes() -> Result<(), Box<dyn Error>> {
+    let words = words::WordleWords::new(&source::FixedSource::new("cigar"), 5)?;
+    let mut game = game::Game::new(words, DEFAULT_TRIES, false);
+    let mut renderer = display::PlainDisplay::new(Vec::new(), lang::Language::English);
+
+    renderer.draw_notice("Playing seed 7")?;
+    renderer.begin_game(&game)?;
+    renderer.draw_ui(game.rows())?;
+    let mut input = std::io::Cursor::new("zzzzz\ncrane\n");
+    let guess = get_user_guess(&mut input, &mut renderer, &game)?;
+    game.submit(&guess)?;
+    renderer.draw_ui(game.rows())?;
+    game.submit("cigar")?;
+    renderer.draw_ui(game.rows())?;
+    renderer.draw_end_screen(&GameEnd::Won, game.solution())?;
+    renderer.draw_stats(&stats::Stats::default(), Some(2))?;
+    renderer.draw_summary("Rustle #7 2/6", Some(7))?;
+
+    let output = String::from_utf8(renderer.into_inner())?;
+    assert!(!output.contains('\x1b'));
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "Playing seed 7");
+    assert_eq!(lines[1], lang::Language::English.enter_prompt());
+    assert!(lines[2].contains("zzzzz"));
+    assert_eq!(lines[3..6], ["CRANE  GYY..", "CIGAR  GGGGG", "WINNER! Word was \"CIGAR\""]);
+    assert!(output.contains("GUESS DISTRIBUTION"));
+    assert!(output.ends_with("Rustle #7 2/6\n\nPlayed seed 7, challenge a friend with \"rustle --seed 7\"\n"));
+
+    Ok(())
+}
+
+#[test]
+fn renderers_are_told_about_rejected_guesses() -> Result<(), Box<dyn Error>> {
+    let words = words::WordleWords::new(&source::RandomSource::new(Some(1)), 5)?;
+    let mut game = game::Game::new(words, DEFAULT_TRIES, false);
+    let mut renderer = RecordingRenderer::new(lang::Language::English);
+
+    let mut input = std::io::Cursor::new("zzzzz\ncran\ncrane\n");
+    assert_eq!(get_user_guess(&mut input, &mut renderer, &game)?, "crane");
+    assert_eq!(renderer.errors().len(), 2);
+    assert!(renderer.errors()[0].contains("Invalid word \"zzzzz\""));
+    assert!(renderer.errors()[1].contains("Not enough letters"));
+
+    game.submit("crane")?;
+    renderer.draw_ui(game.rows())?;
+
+    let [RenderEvent::Prompt(prompt), _, _, RenderEvent::Board(rows), RenderEvent::Keyboard(keys)] = renderer.events.as_slice() else { panic!("unexpected events: {:?}", renderer.events) };
+    assert_eq!(prompt, lang::Language::English.enter_prompt());
+    assert_eq!(rows.len(), usize::from(DEFAULT_TRIES));
+    assert_eq!(rows[0].iter().map(Letter::value).collect::<String>(), "crane");
+    assert_eq!(keys.len(), keyboard::QWERTY_ROWS.len());
+    assert!(keys.concat().iter().any(|key| key.value() == 'c' && key.status() != &LetterState::NotExists));
+
+    renderer.draw_end_screen(&GameEnd::Quit, game.solution())?;
+    assert_eq!(renderer.events.last(), Some(&RenderEvent::EndScreen(GameEnd::Quit, game.solution().to_string())));
+
+    let stats = stats::Stats::default();
+    renderer.draw_stats(&stats, None)?;
+    assert_eq!(renderer.events.last(), Some(&RenderEvent::Stats(stats, None)));
+
+    renderer.draw_analysis(&solver::analyze(&game))?;
+    let Some(RenderEvent::Analysis(analysis)) = renderer.events.last() else { panic!("unexpected events: {:?}", renderer.events) };
+    assert_eq!(analysis[0].guess, "crane");
+
+    Ok(())
+}
+
+#[test]
+fn guesses_can_be_typed_a_key_at_a_time() -> Result<(), Box<dyn Error>> {
+    let words = words::WordleWords::new(&source::FixedSource::new("cigar"), 5)?;
+    let game = game::Game::new(words, DEFAULT_TRIES, false);
+
+    let keys = "cran".chars().map(Key::Char)
+        .chain([Key::Enter, Key::Char('1'), Key::Char('E'), Key::Char('s'), Key::Char('?'), Key::Backspace, Key::Char('e'), Key::Enter])
+        .collect();
+    let mut renderer = RecordingRenderer::with_keys(lang::Language::English, keys);
+    assert_eq!(get_user_guess_interactive(&mut renderer, &game)?, Some(String::from("crane")));
+
+    let statuses: Vec<_> = renderer.events.iter().filter_map(|event| match event {
+        RenderEvent::PendingGuess(0, typed, status) => Some((typed.as_str(), status.as_str())),
+        _ => None
+    }).collect();
+    let prompt = lang::Language::English.type_prompt();
+    assert_eq!(statuses.len(), 12);
+    assert_eq!(statuses[4], ("cran", prompt));
+    assert_eq!(statuses[5].0, "cran");
+    assert!(statuses[5].1.contains("Not enough letters"));
+    assert_eq!(statuses[6], statuses[5]);
+    assert_eq!(statuses[7], ("crane", prompt));
+    assert_eq!(statuses[8], ("crane", prompt));
+    assert_eq!(statuses[9], ("crane", solver::describe_hint(&game).as_str()));
+    assert_eq!(statuses[10], ("cran", prompt));
+
+    let mut renderer = RecordingRenderer::with_keys(lang::Language::English, vec![Key::Char('c'), Key::Quit]);
+    assert_eq!(get_user_guess_interactive(&mut renderer, &game)?, None);
+
+    let mut renderer = RecordingRenderer::with_keys(lang::Language::English, vec![Key::Char('c')]);
+    assert!(get_user_guess_interactive(&mut renderer, &game).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn solutions_missing_from_the_word_lists_can_be_guessed() -> Result<(), Box<dyn Error>> {
+    let words = words::WordleWords::new(&source::FixedSource::new("qzxjv"), 5)?;
+    assert!(words.is_valid_guess("qzxjv"));
+    assert!(words.get_wordlist().contains(&String::from("qzxjv")));
+
+    // Once the colors rule everything else out, the solver suggests it like any other answer.
+    let mut solver = solver::Solver::new(&words, false);
+    solver.apply("qzxjw", &score_guess("qzxjw", "qzxjv"));
+    solver.apply("crane", &score_guess("crane", "qzxjv"));
+    assert_eq!(solver.candidates(), ["qzxjv"]);
+    assert_eq!(solver.rank().iter().find(|guess| guess.is_candidate).map(|guess| guess.word.as_str()), Some("qzxjv"));
+
+    let mut game = game::Game::new(words, DEFAULT_TRIES, false);
+    assert!(game.validate("qzxjw").is_err());
+    game.submit("qzxjv")?;
+    assert_eq!(game.outcome(), game::Outcome::Won(1));
+
+    Ok(())
+}
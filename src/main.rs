@@ -1,92 +1,503 @@
-use std::{str, env, process, io};
-use rustle::display::{TermFormatter, RustleDisplay};
-use rustle::words::WordleWords;
-use rustle::{Letter, LetterState, get_user_guess, process_guess};
+use std::{env, process, io, thread};
+use std::ffi::OsString;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use clap::error::ErrorKind;
+use rustle::cli::{self, Command, PlayArgs, PuzzleArgs, WordArgs, SolveArgs, ShareArgs, WordsArgs, BenchArgs, StrategyName};
+use rustle::config::Config;
+use rustle::display::{TermFormatter, RustleDisplay, PlainDisplay, restore_terminal_on_panic};
+use rustle::words::{self, WordleWords};
+use rustle::stats::Stats;
+use rustle::cache::SolutionCache;
+use rustle::source::{SolutionSource, NytSource, RandomSource, FallbackChain};
+use rustle::share::{ShareFormat, share_text, share_title};
+use rustle::error::RustleError;
+use rustle::game::{Game, GuessError, Outcome};
+use rustle::save::SavedGame;
+use rustle::solver::{analyze, describe_hint, Solver, Strategy, GreedyEntropy, Minimax, FixedOpener};
+use rustle::bench::run_bench;
+use rustle::lang::{self, Language};
+use rustle::render::{GameEnd, Renderer};
+use rustle::theme::Theme;
+use rustle::{get_user_guess, get_user_guess_interactive};
 
 fn main() {
+    let args: Vec<OsString> = env::args_os().collect();
+    let no_color = env::var_os("NO_COLOR");
+
+    // Errors go to stderr, so they're colored when that's a terminal. Until the command line is
+    // parsed, --color has to be picked out of it by hand for errors in the config file.
+    let early_color = cli::early_color_choice(&args, env::var_os("RUSTLE_COLOR").as_deref());
+
+    let config = match load_config(&args) {
+        Ok(config) => config,
+        Err(err) => exit_with_error(&err, early_color.use_color(io::stderr().is_terminal(), no_color.as_deref()))
+    };
+
+    let cli = match cli::parse_args_with_config(args, &config) {
+        Ok(cli) => cli,
+        // Mistakes in the arguments exit with status 1, while --help and --version exit normally.
+        Err(err) => {
+            let _ = err.print();
+            process::exit(match err.kind() {
+                ErrorKind::DisplayHelp | ErrorKind::DisplayVersion => 0,
+                _ => 1
+            })
+        }
+    };
+
+    let color = cli.color.use_color(io::stdout().is_terminal(), no_color.as_deref());
+
+    if let Err(err) = run(cli.command, &config, color) {
+        exit_with_error(&err, cli.color.use_color(io::stderr().is_terminal(), no_color.as_deref()))
+    }
+}
+
+// Prints an error and exits with a status that says what kind of failure it was.
+fn exit_with_error(err: &RustleError, color: bool) -> ! {
+    let (kind, exit_code) = match err {
+        RustleError::Network(_) => ("Network error", 2),
+        RustleError::Parse(_) => ("Invalid data", 3),
+        RustleError::Wordlist(_) => ("Word list error", 4),
+        RustleError::Terminal(_) => ("Terminal error", 5),
+        RustleError::InvalidGuess(_) => ("Invalid guess", 6),
+        RustleError::Storage(_) => ("Storage error", 7)
+    };
+
+    eprintln!("{}{}:{} {}", TermFormatter::RedBold.as_str_if(color), kind, TermFormatter::Clear.as_str_if(color), err);
+    process::exit(exit_code)
+}
+
+// Loads the config file given with --config (or RUSTLE_CONFIG), which has to exist, or else the
+// default one if there is one.
+fn load_config(args: &[OsString]) -> Result<Config, RustleError> {
+    let explicit = cli::config_arg(args).or_else(|| env::var_os("RUSTLE_CONFIG").filter(|path| !path.is_empty()).map(PathBuf::from));
+
+    match explicit {
+        Some(path) => Config::load(&path, true),
+        None => match Config::default_path() {
+            Some(path) => Config::load(&path, false),
+            None => Ok(Config::default())
+        }
+    }
+}
+
+// Runs whatever the command line asked for. `color` says whether stdout is drawn on in color, or
+// gets plain text.
+fn run(command: Command, config: &Config, color: bool) -> Result<(), RustleError> {
+    match command {
+        Command::Play(args) if args.stats => stats(args.words.lang, args.theme, config, color),
+        Command::Play(args) => play(&args, config, color),
+        Command::Stats(args) => stats(args.lang, args.theme, config, color),
+        Command::Solve(args) => solve(&args),
+        Command::Share(args) => share(&args, config, color),
+        Command::Words(args) => list_words(&args),
+        Command::Bench(args) => bench(&args),
+        Command::Prefetch(args) => prefetch(args.days, config, color)
+    }
+}
+
+// Shows the statistics screen for `rustle stats`.
+fn stats(language: Language, theme: Theme, config: &Config, color: bool) -> Result<(), RustleError> {
     println!();
 
-    let args: Vec<String> = env::args().collect();
-    let wordle_words = WordleWords::new(args.len() > 1 && args[1].to_lowercase() == "--offline").unwrap_or_else(|err| {
-        println!("Failed to initialize words: {}", err.to_string());
-        process::exit(1)
-    });
+    let stats = match config.stats_path() {
+        Some(path) => Stats::load(&path)?,
+        None => Stats::default()
+    };
+
+    text_renderer(language, theme, color).draw_stats(&stats, None)
+}
+
+// Draws on the terminal in color, or prints plain text when color is off. Games start out with no
+// board, until Renderer::begin_game is called.
+fn text_renderer(language: Language, theme: Theme, color: bool) -> Box<dyn Renderer> {
+    if color {
+        Box::new(RustleDisplay::new(language, theme))
+    } else {
+        Box::new(PlainDisplay::new(io::stdout(), language))
+    }
+}
+
+// Plays a game, which is what runs when no command is given.
+fn play(args: &PlayArgs, config: &Config, color: bool) -> Result<(), RustleError> {
+    println!();
+
+    // Draw the board when stdout is a terminal, and print each scored guess as a line of text otherwise.
+    let mut renderer = text_renderer(args.words.lang, args.theme, color);
+
+    let wordle_words = load_words(&args.puzzle, &args.words, config, renderer.as_mut())?;
+
+    if let Some(seed) = wordle_words.get_seed() {
+        renderer.draw_notice(&format!("Playing seed {}", seed))?;
+    }
+
+    // Pick up where the last game of this puzzle left off, unless asked to start over. Random games
+    // are only saved when their seed was asked for, since an unseeded one can't be launched again
+    // and its save would never be cleaned up.
+    let resumable = wordle_words.get_puzzle_date().is_some() || args.puzzle.seed.is_some();
+    let puzzle_key = SavedGame::puzzle_key(&wordle_words).filter(|_| resumable);
+    let save_path = config.games_dir().zip(puzzle_key.as_ref()).map(|(dir, key)| SavedGame::path(&dir, key));
+
+    let mut game = match &save_path {
+        Some(path) => restore_game(path, args.new, wordle_words, args.tries, args.hard, renderer.as_mut())?,
+        None => Game::new(wordle_words, args.tries, args.hard)
+    };
+
+    if args.hint {
+        if game.is_over() {
+            return renderer.draw_notice("This puzzle is already over, use --new to play it again.")
+        }
+
+        return renderer.draw_notice(&describe_hint(&game))
+    }
 
-    let wordle_solution: &str = &wordle_words.get_solution().clone();
+    // A finished daily puzzle is only shown again, not replayed.
+    let already_finished = game.is_over();
+    let mut save_error: Option<RustleError> = None;
 
     let stdin = io::stdin();
 
-    const MAX_TRIES: u8 = 6;
+    renderer.begin_game(&game)?;
+    renderer.draw_ui(game.rows())?;
+
+    // Type straight into the tiles when attached to a terminal, fall back to line reads for piped
+    // input or when the board is printed as plain text.
+    let interactive = stdin.is_terminal() && color;
+
+    if interactive {
+        restore_terminal_on_panic();
+    }
+
+    while !game.is_over() {
+        let guess = if interactive {
+            match get_user_guess_interactive(renderer.as_mut(), &game)? {
+                Some(guess) => guess,
+                None => {
+                    renderer.draw_end_screen(&GameEnd::Quit, game.solution())?;
+
+                    if let Some(err) = save_error {
+                        renderer.draw_warning(&format!("Failed to save game: {}", err))?;
+                    }
+
+                    return Ok(())
+                }
+            }
+        } else {
+            get_user_guess(&mut stdin.lock(), renderer.as_mut(), &game)?
+        };
+
+        game.submit(&guess)?;
 
-    let mut guess_list: [Vec<Letter>; 6] = [
-        vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)], 
-        vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)],
-        vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)],
-        vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)],
-        vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)],
-        vec![Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists), Letter::new(' ', LetterState::NotExists)]
-    ];
+        if let (Some(path), Some(key)) = (&save_path, &puzzle_key) {
+            // Save failures are held back until the game is over so they don't draw over the board.
+            if let Err(err) = save_game(path, key, &game) {
+                save_error = Some(err);
+            }
+        }
 
-    let mut rustle_display = match RustleDisplay::initialize_ui(wordle_words.is_offline()) {
-        Ok(res) => res,
-        Err(e) => panic!("Failed to initialize display: {}", e.to_string())
+        renderer.draw_ui(game.rows())?;
+    }
+
+    let end = match game.outcome() {
+        _ if already_finished => GameEnd::AlreadyPlayed,
+        Outcome::Won(_) => GameEnd::Won,
+        _ => GameEnd::Lost(game.max_tries())
     };
 
-    rustle_display.draw_logo().unwrap_or_else(|err| {
-        panic!("Failed to draw logo: {}", err.to_string())
-    });
+    renderer.draw_end_screen(&end, game.solution())?;
 
-    rustle_display.draw_ui(&guess_list).unwrap_or_else(|err| {
-        panic!("Failed to draw logo: {}", err.to_string())
-    });
+    if let Some(err) = save_error {
+        renderer.draw_warning(&format!("Failed to save game: {}", err))?;
+    }
 
-    let mut guess = get_user_guess(&mut stdin.lock(), &mut rustle_display, &wordle_words).unwrap_or_else(|err| {
-        panic!("Failed to draw logo: {}", err.to_string())
-    });
+    if !already_finished {
+        record_stats(match game.outcome() {
+            Outcome::Won(guesses) => Some(guesses),
+            _ => None
+        }, renderer.as_mut(), config)?;
+    }
 
-    for attempt in 1..=MAX_TRIES {
-        process_guess(&guess, &mut guess_list, wordle_solution, attempt);
+    draw_summary(&game, args.share_format, renderer.as_mut())?;
 
-        rustle_display.draw_ui(&guess_list).unwrap_or_else(|err| {
-            panic!("Failed to draw logo: {}", err.to_string())
-        });
+    if args.analyze {
+        renderer.draw_analysis(&analyze(&game))?;
+    }
 
-        if guess == wordle_solution {
-            println!(
-                "{}WINNER!{} Word was \"{}{}{}\"", 
-                TermFormatter::GreenBold.as_str(),
-                TermFormatter::Clear.as_str(),
-                TermFormatter::DefaultBold.as_str(),
-                wordle_solution.to_uppercase(),
-                TermFormatter::Clear.as_str()
-            );
+    Ok(())
+}
 
-            rustle_display.terminate_ui().unwrap_or_else(|err| {
-                panic!("Failed to draw logo: {}", err.to_string())
-            });
+// Loads the saved game at `path` into a new game, falling back to a fresh one (with the settings
+// that were asked for) if there's nothing to resume. An unfinished game has to be resumed with the
+// settings it was saved with, while a finished one is only shown again so its settings don't matter.
+fn restore_game(path: &Path, new_game: bool, wordle_words: WordleWords, max_tries: u8, hard_mode: bool, renderer: &mut dyn Renderer) -> Result<Game, RustleError> {
+    if new_game {
+        if let Err(err) = SavedGame::discard(path) {
+            renderer.draw_warning(&err.to_string())?;
+        }
+
+        return Ok(Game::new(wordle_words, max_tries, hard_mode))
+    }
+
+    let saved = match SavedGame::load(path) {
+        Ok(Some(saved)) if saved.matches(wordle_words.get_solution()) => saved,
+        Ok(Some(_)) => {
+            renderer.draw_notice("The saved game is for a different solution, starting a new game.")?;
+            return Ok(Game::new(wordle_words, max_tries, hard_mode))
+        },
+        Ok(None) => return Ok(Game::new(wordle_words, max_tries, hard_mode)),
+        Err(err) => {
+            renderer.draw_warning(&format!("{} Starting a new game.", err))?;
+            return Ok(Game::new(wordle_words, max_tries, hard_mode))
+        }
+    };
+
+    let mut game = Game::new(wordle_words, saved.max_tries(), saved.hard_mode());
+
+    if let Err(err) = saved.replay(&mut game) {
+        renderer.draw_warning(&format!("Failed to resume the saved game ({}), starting a new game.", err))?;
+        return Ok(Game::new(game.into_words(), max_tries, hard_mode))
+    }
+
+    if !game.is_over() {
+        saved.check_settings(max_tries, hard_mode)?;
+    }
+
+    if !game.is_over() && !saved.guesses().is_empty() {
+        let guesses = saved.guesses().len();
+        renderer.draw_notice(&format!("Resuming your game after {} guess{}, use --new to start over.", guesses, if guesses == 1 { "" } else { "es" }))?;
+    }
+
+    Ok(game)
+}
+
+// Saves the game after a guess. Finished daily puzzles are kept so they can't be replayed, but any
+// other finished game is forgotten so its seed can be played again.
+fn save_game(path: &Path, puzzle_key: &str, game: &Game) -> Result<(), RustleError> {
+    if game.is_over() && game.words().get_puzzle_date().is_none() {
+        return SavedGame::discard(path)
+    }
+
+    SavedGame::from_game(puzzle_key, game).save(path)
+}
 
-            return
-        } else if attempt == MAX_TRIES {
+// Shows the share text for a finished game, plus how to replay it for seeded games.
+fn draw_summary(game: &Game, share_format: ShareFormat, renderer: &mut dyn Renderer) -> Result<(), RustleError> {
+    let wordle_words = game.words();
+    let text = share_text(game.rows(), &share_title(wordle_words.get_puzzle_number(), wordle_words.get_seed()), game.hard_mode(), share_format);
+
+    renderer.draw_summary(&text, wordle_words.get_seed())
+}
+
+// Picks the solution for the puzzle that was asked for and loads the words to play it with. A
+// requested daily puzzle has to come from the NYT, otherwise today's puzzle falls back to a random one.
+fn load_words(puzzle: &PuzzleArgs, word_args: &WordArgs, config: &Config, renderer: &mut dyn Renderer) -> Result<WordleWords, RustleError> {
+    let today = chrono::Local::now().date_naive();
+    let requested_date = puzzle.requested_date(today);
+    let custom_lists = word_args.custom_lists()?;
+
+    // The NYT only publishes five letter puzzles, in English.
+    let daily_available = word_args.length == words::DEFAULT_WORD_LENGTH && word_args.lang == Language::English && custom_lists.is_none();
+
+    if !daily_available && requested_date.is_some() {
+        return Err(RustleError::Wordlist(format!("Daily puzzles are only available with the bundled {} letter English words.", words::DEFAULT_WORD_LENGTH)))
+    }
+
+    let solution_cache = config.cache_dir().map(|dir| SolutionCache::new(dir, puzzle.refresh));
+
+    let solution_source: Box<dyn SolutionSource> = match requested_date {
+        Some(date) => Box::new(NytSource::new(date, solution_cache)),
+        None if puzzle.offline || puzzle.seed.is_some() || !daily_available => Box::new(RandomSource::new(puzzle.seed)),
+        None => Box::new(FallbackChain::new(vec![
+            Box::new(NytSource::new(today, solution_cache)),
+            Box::new(RandomSource::new(None))
+        ]))
+    };
+
+    let wordle_words = match custom_lists {
+        Some((wordlist, answerlist)) => WordleWords::with_lists(solution_source.as_ref(), word_args.lang, word_args.length, wordlist, answerlist)?,
+        None => WordleWords::with_language(solution_source.as_ref(), word_args.lang, word_args.length)?
+    };
+
+    // Say why the daily puzzle couldn't be used before anything else is shown.
+    for err in wordle_words.get_fallback_errors() {
+        renderer.draw_warning(&err.to_string())?;
+    }
+
+    Ok(wordle_words)
+}
+
+// Caches the daily puzzles for today and the following days for `rustle prefetch`. Puzzles are
+// always fetched again, so there's nothing to refresh.
+fn prefetch(days: u64, config: &Config, color: bool) -> Result<(), RustleError> {
+    let Some(solution_cache) = config.cache_dir().map(|dir| SolutionCache::new(dir, false)) else {
+        return Err(RustleError::Storage(String::from("Unable to locate a cache directory to prefetch solutions into.")))
+    };
+
+    let today = chrono::Local::now().date_naive();
+
+    // Failures go to stderr, so they're only colored when that's a terminal too.
+    let error_color = color && io::stderr().is_terminal();
+
+    for (date, result) in NytSource::new(today, Some(solution_cache)).prefetch(today, days) {
+        match result {
+            Ok(()) => println!("{} cached", date),
+            Err(err) => eprintln!("{} {}{}{}", date, TermFormatter::RedFg.as_str_if(error_color), err, TermFormatter::Clear.as_str_if(error_color))
+        }
+    }
+
+    Ok(())
+}
+
+// Suggests guesses for a game played somewhere else, from the guesses and colors it's had so far.
+fn solve(args: &SolveArgs) -> Result<(), RustleError> {
+    let (wordlist, answerlist) = args.words.lists()?;
+    let mut solver = Solver::with_words(&wordlist, &answerlist, args.hard);
+
+    for row in &args.rows {
+        if row.guess.chars().count() != args.words.length || !row.guess.chars().all(|letter| args.words.lang.is_letter(letter)) {
+            return Err(RustleError::InvalidGuess(GuessError::InvalidWord(row.guess.clone())))
+        }
+
+        solver.apply(&row.guess, &row.states);
+    }
+
+    let candidates = solver.candidates();
+
+    match candidates.len() {
+        0 => println!("No word in the word list fits those colors."),
+        1 => println!("It can only be {}", lang::to_uppercase_word(candidates[0])),
+        remaining if remaining <= 10 => println!(
+            "{} possible answers left: {}",
+            remaining,
+            candidates.iter().map(|word| lang::to_uppercase_word(word)).collect::<Vec<String>>().join(", ")
+        ),
+        remaining => println!("{} possible answers left", remaining)
+    }
+
+    if candidates.len() > 1 {
+        println!("\nBest guesses:");
+
+        for (index, guess) in solver.rank().iter().take(usize::try_from(args.top).unwrap_or(usize::MAX)).enumerate() {
             println!(
-                "Failed to guess in {} tries! Word was \"{}{}{}\"",
-                MAX_TRIES,
-                TermFormatter::DefaultBold.as_str(),
-                wordle_solution.to_uppercase(),
-                TermFormatter::Clear.as_str()
+                "{:>3}. {}  {:.2} bits, at most {} left{}",
+                index + 1,
+                lang::to_uppercase_word(&guess.word),
+                guess.entropy,
+                guess.worst_case,
+                if guess.is_candidate { " (could be the answer)" } else { "" }
             );
+        }
+    }
+
+    Ok(())
+}
 
-            rustle_display.terminate_ui().unwrap_or_else(|err| {
-                panic!("Failed to draw logo: {}", err.to_string())
-            });
+// Prints the share text for a finished puzzle. Only daily puzzles stay saved once they're finished.
+fn share(args: &ShareArgs, config: &Config, color: bool) -> Result<(), RustleError> {
+    let mut renderer = text_renderer(args.words.lang, Theme::default(), color);
+    let wordle_words = load_words(&args.puzzle, &args.words, config, renderer.as_mut())?;
 
-            return
-        } else {
-            guess = get_user_guess(&mut stdin.lock(), &mut rustle_display, &wordle_words).unwrap_or_else(|err| {
-                panic!("Failed to draw logo: {}", err.to_string())
-            });
+    let saved = match config.games_dir().zip(SavedGame::puzzle_key(&wordle_words)) {
+        Some((dir, key)) => SavedGame::load(&SavedGame::path(&dir, &key))?,
+        None => None
+    };
+
+    let Some(saved) = saved.filter(|saved| saved.matches(wordle_words.get_solution())) else {
+        return Err(RustleError::Storage(String::from("There's no saved game for this puzzle to share, play it first.")))
+    };
+
+    let mut game = Game::new(wordle_words, saved.max_tries(), saved.hard_mode());
+    saved.replay(&mut game)?;
+
+    if !game.is_over() {
+        return Err(RustleError::Storage(String::from("This puzzle isn't finished yet, finish it before sharing.")))
+    }
+
+    draw_summary(&game, args.share_format, renderer.as_mut())
+}
+
+// Prints the words that can be guessed, or with --solutions the words that can be the answer, one per line.
+fn list_words(args: &WordsArgs) -> Result<(), RustleError> {
+    let (mut wordlist, answerlist) = args.words.lists()?;
+
+    let words = if args.solutions {
+        answerlist
+    } else {
+        wordlist.extend(answerlist);
+        wordlist.sort_unstable();
+        wordlist.dedup();
+        wordlist
+    };
+
+    let mut stdout = io::stdout().lock();
+
+    for word in words {
+        // Stop quietly if whatever the list is piped into (e.g. head) stops reading.
+        if writeln!(stdout, "{}", word).is_err() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+// Plays a solver strategy against every answer for `rustle bench`, printing the results as JSON.
+fn bench(args: &BenchArgs) -> Result<(), RustleError> {
+    let (wordlist, answerlist) = args.words.lists()?;
+
+    let threads = match args.threads {
+        Some(threads) => usize::try_from(threads).unwrap_or(usize::MAX),
+        None => thread::available_parallelism().map_or(1, usize::from)
+    };
+
+    let mut strategy: Box<dyn Strategy> = match args.strategy {
+        StrategyName::Entropy => Box::new(GreedyEntropy),
+        StrategyName::Minimax => Box::new(Minimax)
+    };
+
+    if let Some(opener) = &args.opener {
+        let opener = opener.to_lowercase();
+
+        // The opener has to be a word the solver could have guessed.
+        if !wordlist.contains(&opener) && !answerlist.contains(&opener) {
+            return Err(RustleError::InvalidGuess(GuessError::InvalidWord(opener)))
         }
+
+        strategy = Box::new(FixedOpener { opener, then: strategy });
+    }
+
+    let report = run_bench(&wordlist, &answerlist, strategy.as_ref(), args.tries, args.hard, threads);
+
+    println!("{}", serde_json::to_string_pretty(&report).map_err(|err| RustleError::Parse(err.to_string()))?);
+
+    Ok(())
+}
+
+// Updates the stats file with the outcome of the finished game and shows the statistics screen.
+// Stats failures are reported but never take down a finished game.
+fn record_stats(won_in: Option<u8>, renderer: &mut dyn Renderer, config: &Config) -> Result<(), RustleError> {
+    let Some(stats_path) = config.stats_path() else {
+        return renderer.draw_warning("Unable to locate a data directory, stats will not be saved.")
+    };
+
+    let mut stats = match Stats::load(&stats_path) {
+        Ok(stats) => stats,
+        Err(err) => {
+            return renderer.draw_warning(&format!("Failed to load stats: {}", err))
+        }
+    };
+
+    match won_in {
+        Some(guesses) => stats.record_win(guesses),
+        None => stats.record_loss()
+    }
+
+    if let Err(err) = stats.save(&stats_path) {
+        renderer.draw_warning(&format!("Failed to save stats: {}", err))?;
     }
 
+    renderer.draw_stats(&stats, won_in)
 }
 
 #[cfg(test)]
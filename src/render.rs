@@ -0,0 +1,205 @@
+use crate::error::RustleError;
+use crate::game::Game;
+use crate::keyboard;
+use crate::lang::Language;
+use crate::solver::RowAnalysis;
+use crate::stats::Stats;
+use crate::Letter;
+
+// Draws a game. The game logic only ever talks to a Renderer, so it can run against the terminal
+// (display::RustleDisplay), plain text (display::PlainDisplay) or anything else, like the
+// RecordingRenderer used by the tests.
+pub trait Renderer {
+    // Called once before the board is first drawn, to make room for it.
+    fn begin_game(&mut self, _game: &Game) -> Result<(), RustleError> {
+        Ok(())
+    }
+
+    // Draws the guess rows, with unplayed rows as blank NotExists placeholders.
+    fn draw_board(&mut self, guess_list: &[Vec<Letter>]) -> Result<(), RustleError>;
+
+    // Draws the keyboard with the best result seen for every letter guessed so far.
+    fn draw_keyboard(&mut self, guess_list: &[Vec<Letter>]) -> Result<(), RustleError>;
+
+    // Tells the player why a guess was turned away.
+    fn draw_error(&mut self, message: &str) -> Result<(), RustleError>;
+
+    // Asks for the next guess when guesses are read a line at a time.
+    fn draw_prompt(&mut self, prompt: &str) -> Result<(), RustleError>;
+
+    // Prints a message outside of the board, like the seed being played or a hint. Only called
+    // before begin_game or after draw_end_screen, so it never draws over the board.
+    fn draw_notice(&mut self, message: &str) -> Result<(), RustleError>;
+
+    // Reports something that went wrong without stopping the game, like a save that couldn't be
+    // written. Goes to stderr, so it stays out of output that's piped somewhere.
+    fn draw_warning(&mut self, message: &str) -> Result<(), RustleError>;
+
+    // Called once before a guess is typed into the tiles, for anything that has to be set up first,
+    // like putting the terminal into raw mode.
+    fn begin_pending_guess(&mut self) -> Result<(), RustleError> {
+        Ok(())
+    }
+
+    // Called once the guess has been typed (or abandoned), undoing begin_pending_guess.
+    fn end_pending_guess(&mut self) -> Result<(), RustleError> {
+        Ok(())
+    }
+
+    // Waits for the next key pressed while a guess is being typed into the tiles.
+    fn read_key(&mut self) -> Result<Key, RustleError>;
+
+    // Shows the letters typed so far in the tiles of row `row`, with a status line underneath
+    // (the prompt, a hint, or why the guess was turned away when `is_error` is set).
+    fn draw_pending_guess(&mut self, guess_list: &[Vec<Letter>], row: usize, typed: &str, status_msg: &str, is_error: bool) -> Result<(), RustleError>;
+
+    // Says how the game ended and what the solution was, and leaves the screen ready for whatever
+    // is printed after the game.
+    fn draw_end_screen(&mut self, end: &GameEnd, solution: &str) -> Result<(), RustleError>;
+
+    // Draws the statistics screen. `latest_win` is the number of guesses in the game that was just
+    // won, if any, so its histogram bar can be highlighted.
+    fn draw_stats(&mut self, stats: &Stats, latest_win: Option<u8>) -> Result<(), RustleError>;
+
+    // Draws the post-game review from solver::analyze.
+    fn draw_analysis(&mut self, analysis: &[RowAnalysis]) -> Result<(), RustleError>;
+
+    // Prints the share text for a finished game, plus how to replay it when it was a seeded game.
+    fn draw_summary(&mut self, share_text: &str, seed: Option<u64>) -> Result<(), RustleError>;
+
+    fn draw_ui(&mut self, guess_list: &[Vec<Letter>]) -> Result<(), RustleError> {
+        self.draw_board(guess_list)?;
+        self.draw_keyboard(guess_list)
+    }
+}
+
+// How a game ended, for Renderer::draw_end_screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameEnd {
+    Won,
+    Lost(u8), // Out of tries, with how many there were.
+    Quit,
+    AlreadyPlayed // A finished daily puzzle that was launched again.
+}
+
+// A key pressed while typing a guess into the tiles, from Renderer::read_key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Backspace,
+    Enter,
+    Quit // Esc or Ctrl+C.
+}
+
+// Everything a RecordingRenderer was asked to draw, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderEvent {
+    Board(Vec<Vec<Letter>>),
+    Keyboard(Vec<Vec<Letter>>), // The keys of each keyboard row.
+    Error(String),
+    Prompt(String),
+    Notice(String),
+    Warning(String),
+    PendingGuess(usize, String, String), // The row, the letters typed so far and the status line.
+    EndScreen(GameEnd, String),
+    Stats(Stats, Option<u8>),
+    Analysis(Vec<RowAnalysis>),
+    Summary(String, Option<u64>) // The share text and the seed.
+}
+
+// A Renderer that draws nothing and keeps a list of what it was asked to draw, for tests.
+pub struct RecordingRenderer {
+    pub events: Vec<RenderEvent>,
+    keyboard: &'static [&'static str],
+    keys: std::vec::IntoIter<Key> // The keys read_key hands out, in order.
+}
+
+impl RecordingRenderer {
+    pub fn new(language: Language) -> RecordingRenderer {
+        RecordingRenderer { events: Vec::new(), keyboard: language.keyboard(), keys: Vec::new().into_iter() }
+    }
+
+    // A RecordingRenderer that answers read_key with `keys`, as if they were typed.
+    pub fn with_keys(language: Language, keys: Vec<Key>) -> RecordingRenderer {
+        RecordingRenderer { keys: keys.into_iter(), ..RecordingRenderer::new(language) }
+    }
+
+    pub fn errors(&self) -> Vec<&str> {
+        self.events.iter().filter_map(|event| match event {
+            RenderEvent::Error(message) => Some(message.as_str()),
+            _ => None
+        }).collect()
+    }
+}
+
+impl Renderer for RecordingRenderer {
+    fn draw_board(&mut self, guess_list: &[Vec<Letter>]) -> Result<(), RustleError> {
+        self.events.push(RenderEvent::Board(guess_list.to_vec()));
+
+        Ok(())
+    }
+
+    fn draw_keyboard(&mut self, guess_list: &[Vec<Letter>]) -> Result<(), RustleError> {
+        self.events.push(RenderEvent::Keyboard(keyboard::keyboard_rows(guess_list, self.keyboard)));
+
+        Ok(())
+    }
+
+    fn draw_error(&mut self, message: &str) -> Result<(), RustleError> {
+        self.events.push(RenderEvent::Error(message.to_string()));
+
+        Ok(())
+    }
+
+    fn draw_prompt(&mut self, prompt: &str) -> Result<(), RustleError> {
+        self.events.push(RenderEvent::Prompt(prompt.to_string()));
+
+        Ok(())
+    }
+
+    fn draw_notice(&mut self, message: &str) -> Result<(), RustleError> {
+        self.events.push(RenderEvent::Notice(message.to_string()));
+
+        Ok(())
+    }
+
+    fn draw_warning(&mut self, message: &str) -> Result<(), RustleError> {
+        self.events.push(RenderEvent::Warning(message.to_string()));
+
+        Ok(())
+    }
+
+    fn draw_pending_guess(&mut self, _guess_list: &[Vec<Letter>], row: usize, typed: &str, status_msg: &str, _is_error: bool) -> Result<(), RustleError> {
+        self.events.push(RenderEvent::PendingGuess(row, typed.to_string(), status_msg.to_string()));
+
+        Ok(())
+    }
+
+    fn read_key(&mut self) -> Result<Key, RustleError> {
+        self.keys.next().ok_or_else(|| RustleError::Terminal(String::from("Ran out of keys before a guess was entered.")))
+    }
+
+    fn draw_end_screen(&mut self, end: &GameEnd, solution: &str) -> Result<(), RustleError> {
+        self.events.push(RenderEvent::EndScreen(*end, solution.to_string()));
+
+        Ok(())
+    }
+
+    fn draw_stats(&mut self, stats: &Stats, latest_win: Option<u8>) -> Result<(), RustleError> {
+        self.events.push(RenderEvent::Stats(stats.clone(), latest_win));
+
+        Ok(())
+    }
+
+    fn draw_analysis(&mut self, analysis: &[RowAnalysis]) -> Result<(), RustleError> {
+        self.events.push(RenderEvent::Analysis(analysis.to_vec()));
+
+        Ok(())
+    }
+
+    fn draw_summary(&mut self, share_text: &str, seed: Option<u64>) -> Result<(), RustleError> {
+        self.events.push(RenderEvent::Summary(share_text.to_string(), seed));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::RustleError;
+use crate::game::Game;
+use crate::lang::Language;
+use crate::storage;
+use crate::words::WordleWords;
+
+// The layout saves are written in. SavedGame::load turns away saves from a newer Rustle rather than
+// misreading them, so bump this whenever the layout changes.
+pub const SAVE_VERSION: u32 = 1;
+
+// The guesses played so far in a game, so it can be picked back up after Rustle is closed. Finished
+// daily puzzles stay saved so they can't be replayed, any other game is discarded once it's over.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SavedGame {
+    version: u32,
+    puzzle: String, // The puzzle_key() of the game.
+    solution_hash: u64, // Hashed so a peek at the save file doesn't spoil the answer.
+    max_tries: u8,
+    hard_mode: bool,
+    guesses: Vec<String>
+}
+
+impl SavedGame {
+    // Saves live under the XDG data directory (e.g. ~/.local/share/rustle/games/daily-2024-03-15.json).
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("rustle").join("games"))
+    }
+
+    // What a game is saved under: the date of a daily puzzle, or the seed, word length and (for word
+    // packs other than English) language of a random one. Games with custom word lists also carry a
+    // hash of the answers, so editing a list starts a new game. Games with a solution from anywhere
+    // else can't be told apart, so they aren't saved.
+    pub fn puzzle_key(wordle_words: &WordleWords) -> Option<String> {
+        if let Some(date) = wordle_words.get_puzzle_date() {
+            return Some(format!("daily-{}", date.format("%Y-%m-%d")))
+        }
+
+        let mut key = format!("seed-{}-{}", wordle_words.get_seed()?, wordle_words.get_word_length());
+
+        if wordle_words.get_language() != Language::English {
+            key.push_str(&format!("-{}", wordle_words.get_language().code()));
+        }
+
+        if wordle_words.has_custom_lists() {
+            key.push_str(&format!("-custom-{:016x}", solution_hash(&wordle_words.get_answerlist().join("\n"))));
+        }
+
+        Some(key)
+    }
+
+    pub fn path(dir: &Path, puzzle_key: &str) -> PathBuf {
+        dir.join(format!("{}.json", puzzle_key))
+    }
+
+    pub fn from_game(puzzle_key: &str, game: &Game) -> SavedGame {
+        SavedGame {
+            version: SAVE_VERSION,
+            puzzle: puzzle_key.to_string(),
+            solution_hash: solution_hash(game.solution()),
+            max_tries: game.max_tries(),
+            hard_mode: game.hard_mode(),
+            guesses: game.guesses()
+        }
+    }
+
+    // A missing save isn't an error, there's just nothing to resume.
+    pub fn load(path: &Path) -> Result<Option<SavedGame>, RustleError> {
+        if !path.exists() {
+            return Ok(None)
+        }
+
+        let raw_save = fs::read_to_string(path)
+            .map_err(|err| RustleError::Storage(format!("Failed to read saved game \"{}\": {}", path.display(), err)))?;
+        let saved: SavedGame = serde_json::from_str(&raw_save)
+            .map_err(|err| RustleError::Parse(format!("Saved game \"{}\" is corrupt: {}", path.display(), err)))?;
+
+        if saved.version > SAVE_VERSION {
+            return Err(RustleError::Storage(format!(
+                "Saved game \"{}\" is version {}, but this Rustle only understands up to version {}.",
+                path.display(),
+                saved.version,
+                SAVE_VERSION
+            )))
+        }
+
+        Ok(Some(saved))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), RustleError> {
+        storage::write_json(path, self, |err| RustleError::Storage(format!("Failed to write saved game \"{}\": {}", path.display(), err)))
+    }
+
+    pub fn discard(path: &Path) -> Result<(), RustleError> {
+        match fs::remove_file(path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                Err(RustleError::Storage(format!("Failed to remove saved game \"{}\": {}", path.display(), err)))
+            },
+            _ => Ok(())
+        }
+    }
+
+    // Whether this save was made for the solution being played, rather than e.g. a puzzle the NYT
+    // has since changed.
+    pub fn matches(&self, solution: &str) -> bool {
+        self.solution_hash == solution_hash(solution)
+    }
+
+    // A game can only be resumed with the settings it was started with, so asking for different ones
+    // (e.g. `--tries 3` for a game saved with 6) is turned away rather than quietly ignored.
+    pub fn check_settings(&self, max_tries: u8, hard_mode: bool) -> Result<(), RustleError> {
+        if self.max_tries == max_tries && self.hard_mode == hard_mode {
+            return Ok(())
+        }
+
+        let describe = |max_tries: u8, hard_mode: bool| format!("{} tries in {} mode", max_tries, if hard_mode { "hard" } else { "normal" });
+
+        Err(RustleError::Storage(format!(
+            "The saved game for this puzzle was started with {}, not {}. Resume it with --tries {} --hard={}, or use --new to start over.",
+            describe(self.max_tries, self.hard_mode),
+            describe(max_tries, hard_mode),
+            self.max_tries,
+            self.hard_mode
+        )))
+    }
+
+    pub fn max_tries(&self) -> u8 {
+        self.max_tries
+    }
+
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    pub fn guesses(&self) -> &[String] {
+        &self.guesses
+    }
+
+    // Plays the saved guesses into a fresh game, which should have been made with the saved settings.
+    pub fn replay(&self, game: &mut Game) -> Result<(), RustleError> {
+        for guess in &self.guesses {
+            game.submit(guess)?;
+        }
+
+        Ok(())
+    }
+}
+
+// FNV-1a, which unlike the standard library's hasher is guaranteed to give the same hash in every
+// release so older saves keep working.
+pub fn solution_hash(solution: &str) -> u64 {
+    solution.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01B3))
+}
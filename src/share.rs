@@ -0,0 +1,95 @@
+use std::str::FromStr;
+
+use crate::error::RustleError;
+use crate::{Letter, LetterState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareFormat {
+    Emoji, // The standard 🟩🟨⬛ grid.
+    HighContrast, // 🟧🟦⬛, matching the web version's high contrast mode.
+    Ascii // Plain characters for chat tools that mangle emoji.
+}
+
+impl ShareFormat {
+    // The name given to --share-format.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ShareFormat::Emoji => "emoji",
+            ShareFormat::HighContrast => "contrast",
+            ShareFormat::Ascii => "ascii"
+        }
+    }
+
+    fn tile(&self, status: &LetterState) -> &'static str {
+        match (self, status) {
+            (ShareFormat::Emoji, LetterState::Correct) => "🟩",
+            (ShareFormat::Emoji, LetterState::Exists) => "🟨",
+            (ShareFormat::HighContrast, LetterState::Correct) => "🟧",
+            (ShareFormat::HighContrast, LetterState::Exists) => "🟦",
+            (ShareFormat::Ascii, LetterState::Correct) => "G",
+            (ShareFormat::Ascii, LetterState::Exists) => "Y",
+            (ShareFormat::Ascii, _) => ".",
+            (_, _) => "⬛"
+        }
+    }
+}
+
+impl FromStr for ShareFormat {
+    type Err = RustleError;
+
+    fn from_str(format: &str) -> Result<ShareFormat, RustleError> {
+        match format.to_lowercase().as_str() {
+            "emoji" => Ok(ShareFormat::Emoji),
+            "contrast" | "high-contrast" => Ok(ShareFormat::HighContrast),
+            "ascii" => Ok(ShareFormat::Ascii),
+            _ => Err(RustleError::Parse(format!("Unknown share format \"{}\", expected one of: emoji, contrast, ascii", format)))
+        }
+    }
+}
+
+// The title a game is shared under: the daily puzzle number, the seed for a seeded game, or just
+// "offline" for anything else.
+pub fn share_title(puzzle_number: Option<i64>, seed: Option<u64>) -> String {
+    match (puzzle_number, seed) {
+        (Some(number), _) => format!("Rustle {}", group_thousands(number)),
+        (None, Some(seed)) => format!("Rustle seed {}", seed),
+        (None, None) => String::from("Rustle offline")
+    }
+}
+
+// Builds the spoiler-free share text for a game, e.g. "Rustle 1,234 4/6" followed by one row of
+// tiles per guess. Unplayed rows (all NotExists) are left out, and a lost game scores as "X".
+pub fn share_text(guess_list: &[Vec<Letter>], title: &str, hard_mode: bool, format: ShareFormat) -> String {
+    let played: Vec<&Vec<Letter>> = guess_list.iter()
+        .filter(|row| row.iter().any(|letter| letter.status() != &LetterState::NotExists))
+        .collect();
+
+    let won = played.last().is_some_and(|row| row.iter().all(|letter| letter.status() == &LetterState::Correct));
+
+    let score = if won { played.len().to_string() } else { String::from("X") };
+
+    let mut share = format!("{} {}/{}{}\n", title, score, guess_list.len(), if hard_mode { "*" } else { "" });
+
+    for row in played {
+        share.push('\n');
+        for letter in row {
+            share.push_str(format.tile(letter.status()));
+        }
+    }
+
+    share
+}
+
+fn group_thousands(number: i64) -> String {
+    let digits = number.unsigned_abs().to_string();
+    let mut grouped = String::new();
+
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    if number < 0 { format!("-{}", grouped) } else { grouped }
+}
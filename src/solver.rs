@@ -0,0 +1,434 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::thread;
+
+use crate::error::RustleError;
+use crate::game::Game;
+use crate::lang::Language;
+use crate::words::{WordleWords, MAX_WORD_LENGTH};
+use crate::{Letter, LetterState, check_hard_mode, score_guess};
+
+// A word packed into fixed size codes so feedback patterns can be worked out without allocating.
+// Every distinct letter gets its own code, so this works for any alphabet.
+type Encoded = [u8; MAX_WORD_LENGTH];
+
+// A suggested next guess and how much it's expected to narrow things down.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hint {
+    pub word: String,
+    pub entropy: f64, // Expected bits of information from the feedback.
+    pub remaining: usize // Possible answers left before making the guess.
+}
+
+impl Hint {
+    pub fn describe(&self, language: Language) -> String {
+        match self.remaining {
+            1 => language.only_answer(&self.word),
+            remaining => language.hint(&self.word, self.entropy, remaining)
+        }
+    }
+}
+
+// A guess and the colors it was given in a game played elsewhere, written like "crane:.Y..G" with
+// G for green, Y for yellow and . (or -, _, B or X) for gray.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedbackRow {
+    pub guess: String,
+    pub states: Vec<LetterState>
+}
+
+impl FromStr for FeedbackRow {
+    type Err = RustleError;
+
+    fn from_str(row: &str) -> Result<FeedbackRow, RustleError> {
+        let Some((guess, colors)) = row.split_once(':') else {
+            return Err(RustleError::Parse(format!("Invalid row \"{}\", expected a guess and its colors like crane:.Y..G", row)))
+        };
+
+        let states = colors.chars().map(|color| match color.to_ascii_uppercase() {
+            'G' => Ok(LetterState::Correct),
+            'Y' => Ok(LetterState::Exists),
+            '.' | '-' | '_' | 'B' | 'X' => Ok(LetterState::Incorrect),
+            other => Err(RustleError::Parse(format!("Invalid color '{}' in \"{}\", use G for green, Y for yellow and . for gray", other, row)))
+        }).collect::<Result<Vec<LetterState>, RustleError>>()?;
+
+        if states.len() != guess.chars().count() {
+            return Err(RustleError::Parse(format!("\"{}\" has {} letters but {} colors", row, guess.chars().count(), states.len())))
+        }
+
+        Ok(FeedbackRow { guess: guess.to_lowercase(), states })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedGuess {
+    pub word: String,
+    pub entropy: f64,
+    pub worst_case: usize, // The most answers that could be left after the guess.
+    pub is_candidate: bool // Whether the guess could be the answer itself.
+}
+
+// Narrows down the answer from the feedback given so far and ranks guesses by how much they're
+// expected to reveal. Candidates come from the answer pool, or from every valid guess if the answer
+// turns out not to be in the pool (e.g. a newer NYT puzzle).
+#[derive(Clone)]
+pub struct Solver<'a> {
+    guesses: &'a [String],
+    answers: &'a [String],
+    candidates: Vec<&'a str>,
+    played: Vec<Vec<Letter>>,
+    hard_mode: bool,
+    threads: usize // How many threads rank() can use.
+}
+
+impl<'a> Solver<'a> {
+    pub fn new(wordle_words: &'a WordleWords, hard_mode: bool) -> Solver<'a> {
+        Solver::with_words(wordle_words.get_wordlist(), wordle_words.get_answerlist(), hard_mode)
+    }
+
+    pub fn with_words(guesses: &'a [String], answers: &'a [String], hard_mode: bool) -> Solver<'a> {
+        Solver {
+            guesses,
+            answers,
+            candidates: answers.iter().map(String::as_str).collect(),
+            played: Vec::new(),
+            hard_mode,
+            threads: thread::available_parallelism().map_or(1, usize::from)
+        }
+    }
+
+    pub fn with_threads(self, threads: usize) -> Solver<'a> {
+        Solver { threads: threads.max(1), ..self }
+    }
+
+    // A solver that has seen every guess played in a game so far.
+    pub fn for_game(game: &'a Game) -> Solver<'a> {
+        let mut solver = Solver::new(game.words(), game.hard_mode());
+
+        for guess in game.guesses() {
+            solver.apply(&guess, &score_guess(&guess, game.solution()));
+        }
+
+        solver
+    }
+
+    // Keeps only the candidates that would have given exactly this feedback.
+    pub fn apply(&mut self, guess: &str, states: &[LetterState]) {
+        let consistent = |word: &&str| score_guess(guess, word) == states;
+
+        self.candidates.retain(consistent);
+
+        if self.candidates.is_empty() {
+            self.candidates = self.guesses.iter().chain(self.answers.iter()).map(String::as_str).collect();
+            self.candidates.sort_unstable();
+            self.candidates.dedup();
+
+            for (row_guess, row_states) in self.played_feedback().into_iter().chain([(guess.to_string(), states.to_vec())]) {
+                self.candidates.retain(|word| score_guess(&row_guess, word) == row_states);
+            }
+        }
+
+        self.played.push(guess.chars().zip(states.iter()).map(|(char, status)| Letter::new(char, *status)).collect());
+    }
+
+    fn played_feedback(&self) -> Vec<(String, Vec<LetterState>)> {
+        self.played.iter().map(|row| {
+            (row.iter().map(Letter::value).collect(), row.iter().map(|letter| *letter.status()).collect())
+        }).collect()
+    }
+
+    pub fn candidates(&self) -> &[&'a str] {
+        &self.candidates
+    }
+
+    // How many guesses have been applied so far.
+    pub fn played(&self) -> usize {
+        self.played.len()
+    }
+
+    // Every guess allowed right now, best first: highest expected information, then guesses that
+    // could be the answer, then alphabetical so the ranking is always the same.
+    pub fn rank(&self) -> Vec<RankedGuess> {
+        let allowed: Vec<&str> = self.guesses.iter()
+            .map(String::as_str)
+            .filter(|guess| !self.hard_mode || check_hard_mode(guess, &self.played).is_ok())
+            .collect();
+
+        let mut ranked = score_guesses(&allowed, &self.candidates, self.threads);
+
+        ranked.sort_by(|a, b| {
+            entropy_key(b.entropy).cmp(&entropy_key(a.entropy))
+                .then(b.is_candidate.cmp(&a.is_candidate))
+                .then(a.word.cmp(&b.word))
+        });
+
+        ranked
+    }
+
+    // The guess to play without ranking anything. With one or two answers left, guessing one of
+    // them is always at least as good as any other guess.
+    pub fn obvious_guess(&self) -> Option<&'a str> {
+        match self.candidates.as_slice() {
+            [first] | [first, _] => Some(*first),
+            _ => None
+        }
+    }
+
+    pub fn hint(&self) -> Option<Hint> {
+        if let Some(word) = self.obvious_guess() {
+            return Some(Hint {
+                word: word.to_string(),
+                entropy: if self.candidates.len() == 2 { 1.0 } else { 0.0 },
+                remaining: self.candidates.len()
+            })
+        }
+
+        self.rank().into_iter().next().map(|best| Hint {
+            word: best.word,
+            entropy: best.entropy,
+            remaining: self.candidates.len()
+        })
+    }
+}
+
+// A way of picking the next guess, for comparing approaches with `rustle bench`.
+pub trait Strategy: Sync {
+    fn name(&self) -> String;
+
+    // The guess to play next. Only asked when there's no Solver::obvious_guess.
+    fn choose(&self, solver: &Solver) -> Option<String>;
+}
+
+// Always plays the guess with the most expected information, the same guess hint() suggests.
+pub struct GreedyEntropy;
+
+impl Strategy for GreedyEntropy {
+    fn name(&self) -> String {
+        String::from("entropy")
+    }
+
+    fn choose(&self, solver: &Solver) -> Option<String> {
+        solver.rank().into_iter().next().map(|best| best.word)
+    }
+}
+
+// Plays the guess that leaves the fewest answers in the worst case, breaking ties on guesses that
+// could be the answer and then on expected information.
+pub struct Minimax;
+
+impl Strategy for Minimax {
+    fn name(&self) -> String {
+        String::from("minimax")
+    }
+
+    fn choose(&self, solver: &Solver) -> Option<String> {
+        solver.rank().into_iter()
+            .min_by(|a, b| a.worst_case.cmp(&b.worst_case)
+                .then(b.is_candidate.cmp(&a.is_candidate))
+                .then(entropy_key(b.entropy).cmp(&entropy_key(a.entropy)))
+                .then(a.word.cmp(&b.word)))
+            .map(|best| best.word)
+    }
+}
+
+// Always opens with the same word, then hands over to another strategy.
+pub struct FixedOpener {
+    pub opener: String,
+    pub then: Box<dyn Strategy>
+}
+
+impl Strategy for FixedOpener {
+    fn name(&self) -> String {
+        format!("{} opener, then {}", self.opener, self.then.name())
+    }
+
+    fn choose(&self, solver: &Solver) -> Option<String> {
+        if solver.played() == 0 {
+            return Some(self.opener.clone())
+        }
+
+        self.then.choose(solver)
+    }
+}
+
+// How one row of a game went, next to what the solver would have played instead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowAnalysis {
+    pub guess: String,
+    pub states: Vec<LetterState>,
+    pub before: usize, // Possible answers before the guess.
+    pub after: usize, // Possible answers left by its feedback.
+    pub bits: f64, // Information the feedback actually gave, log2(before / after).
+    pub best: Option<Hint> // The solver's pick for the same row.
+}
+
+// Reviews every row of a game. The feedback is read straight off the board, which was scored by
+// process_guess, so the numbers always match the colors that were shown.
+pub fn analyze(game: &Game) -> Vec<RowAnalysis> {
+    analyze_rows(Solver::new(game.words(), game.hard_mode()), &game.rows()[..usize::from(game.attempts())])
+}
+
+pub fn analyze_rows(mut solver: Solver, rows: &[Vec<Letter>]) -> Vec<RowAnalysis> {
+    rows.iter().map(|row| {
+        let guess: String = row.iter().map(Letter::value).collect();
+        let states: Vec<LetterState> = row.iter().map(|letter| *letter.status()).collect();
+
+        let before = solver.candidates().len();
+        let best = solver.hint();
+
+        solver.apply(&guess, &states);
+
+        let after = solver.candidates().len();
+        let bits = if after > 0 && before > 0 { (before as f64 / after as f64).log2() } else { 0.0 };
+
+        RowAnalysis { guess, states, before, after, bits, best }
+    }).collect()
+}
+
+// The hint for the next guess in a game, ready to show to the player.
+pub fn describe_hint(game: &Game) -> String {
+    let language = game.words().get_language();
+
+    match Solver::for_game(game).hint() {
+        Some(hint) => hint.describe(language),
+        None => language.no_hint().to_string()
+    }
+}
+
+// Entropies are compared to 9 decimal places, so guesses that split the candidates the same way
+// tie no matter what order their buckets were summed in.
+fn entropy_key(entropy: f64) -> i64 {
+    (entropy * 1e9).round() as i64
+}
+
+// Works out the feedback every guess would give for every candidate, split across threads.
+fn score_guesses(guesses: &[&str], candidates: &[&str], threads: usize) -> Vec<RankedGuess> {
+    let mut codes: HashMap<char, u8> = HashMap::new();
+    let encoded_candidates: Vec<Encoded> = candidates.iter().map(|word| encode(word, &mut codes)).collect();
+    let encoded_guesses: Vec<Encoded> = guesses.iter().map(|word| encode(word, &mut codes)).collect();
+
+    // Scoring is specialized for each word length so the compiler can unroll it.
+    let score_chunk = match candidates.first().map_or(0, |word| word.chars().count()) {
+        4 => score_chunk::<4>,
+        5 => score_chunk::<5>,
+        6 => score_chunk::<6>,
+        7 => score_chunk::<7>,
+        8 => score_chunk::<8>,
+        _ => return Vec::new()
+    };
+
+    let chunk_size = guesses.len().div_ceil(threads).max(1);
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = guesses.chunks(chunk_size).zip(encoded_guesses.chunks(chunk_size)).map(|(words, encoded)| {
+            let encoded_candidates = &encoded_candidates;
+
+            scope.spawn(move || score_chunk(words, encoded, encoded_candidates))
+        }).collect();
+
+        workers.into_iter().flat_map(|worker| worker.join().expect("Solver thread panicked")).collect()
+    })
+}
+
+fn score_chunk<const N: usize>(words: &[&str], guesses: &[Encoded], candidates: &[Encoded]) -> Vec<RankedGuess> {
+    let mut buckets = vec![0u32; 3usize.pow(N as u32)];
+    let mut unmatched = [0u8; 256];
+
+    words.iter().zip(guesses).map(|(word, guess)| {
+        buckets.iter_mut().for_each(|count| *count = 0);
+
+        for candidate in candidates {
+            buckets[pattern::<N>(guess, candidate, &mut unmatched)] += 1;
+        }
+
+        ranked_guess(word, &buckets)
+    }).collect()
+}
+
+fn ranked_guess(word: &str, buckets: &[u32]) -> RankedGuess {
+    let total = f64::from(buckets.iter().sum::<u32>().max(1));
+
+    let entropy = buckets.iter().filter(|count| **count > 0).map(|count| {
+        let probability = f64::from(*count) / total;
+        -probability * probability.log2()
+    }).sum();
+
+    RankedGuess {
+        word: word.to_string(),
+        entropy,
+        worst_case: buckets.iter().copied().max().unwrap_or(0) as usize,
+        // Only a candidate can come back all green, which is the last pattern.
+        is_candidate: buckets.last().is_some_and(|count| *count > 0)
+    }
+}
+
+fn encode(word: &str, codes: &mut HashMap<char, u8>) -> Encoded {
+    let mut encoded = [0; MAX_WORD_LENGTH];
+
+    for (slot, char) in encoded.iter_mut().zip(word.chars()) {
+        let next_code = u8::try_from(codes.len() + 1).unwrap_or(u8::MAX);
+        *slot = *codes.entry(char).or_insert(next_code);
+    }
+
+    encoded
+}
+
+// The feedback a guess gets against a solution as a base 3 number, first letter most significant:
+// 0 for Incorrect, 1 for Exists and 2 for Correct. Scored the same way as score_guess. `unmatched`
+// counts the solution's letters that weren't an exact match, and is handed back all zero.
+fn pattern<const N: usize>(guess: &Encoded, solution: &Encoded, unmatched: &mut [u8; 256]) -> usize {
+    for index in 0..N {
+        if guess[index] != solution[index] {
+            unmatched[usize::from(solution[index])] += 1;
+        }
+    }
+
+    let mut pattern = 0;
+
+    for index in 0..N {
+        let code = usize::from(guess[index]);
+
+        let state = if guess[index] == solution[index] {
+            2
+        } else if unmatched[code] > 0 {
+            unmatched[code] -= 1;
+            1
+        } else {
+            0
+        };
+
+        pattern = pattern * 3 + state;
+    }
+
+    for code in &solution[..N] {
+        unmatched[usize::from(*code)] = 0;
+    }
+
+    pattern
+}
+
+// The feedback pattern for a pair of words, for checking against score_guess.
+pub fn feedback_pattern(guess: &str, solution: &str) -> usize {
+    let mut codes: HashMap<char, u8> = HashMap::new();
+    let encoded_guess = encode(guess, &mut codes);
+    let encoded_solution = encode(solution, &mut codes);
+
+    let unmatched = &mut [0; 256];
+
+    match guess.chars().count() {
+        4 => pattern::<4>(&encoded_guess, &encoded_solution, unmatched),
+        5 => pattern::<5>(&encoded_guess, &encoded_solution, unmatched),
+        6 => pattern::<6>(&encoded_guess, &encoded_solution, unmatched),
+        7 => pattern::<7>(&encoded_guess, &encoded_solution, unmatched),
+        _ => pattern::<8>(&encoded_guess, &encoded_solution, unmatched)
+    }
+}
+
+// The base 3 number for a row of feedback, matching feedback_pattern.
+pub fn states_pattern(states: &[LetterState]) -> usize {
+    states.iter().fold(0, |pattern, status| pattern * 3 + match status {
+        LetterState::Correct => 2,
+        LetterState::Exists => 1,
+        _ => 0
+    })
+}
@@ -0,0 +1,258 @@
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use rand::Rng;
+
+use crate::cache::SolutionCache;
+use crate::error::RustleError;
+use crate::words::{puzzle_number, SolutionResponse};
+
+pub const NYT_BASE_URL: &str = "https://www.nytimes.com/svc/wordle/v2";
+
+// A solution picked by a SolutionSource. Daily puzzles carry their date and the NYT's details,
+// anything else is an "offline" game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub word: String,
+    pub puzzle_date: Option<NaiveDate>,
+    pub daily_details: Option<SolutionResponse>,
+    pub seed: Option<u64>, // The seed a random solution was picked with, so the game can be replayed.
+    pub fallback_errors: Vec<String> // Why any earlier sources in a FallbackChain were passed over.
+}
+
+impl Solution {
+    pub fn offline(word: String) -> Solution {
+        Solution { word, puzzle_date: None, daily_details: None, seed: None, fallback_errors: Vec::new() }
+    }
+}
+
+// Somewhere a solution can come from. `answerlist` is the curated pool of possible answers, for
+// sources that pick from it.
+pub trait SolutionSource {
+    fn get_solution(&self, answerlist: &[String]) -> Result<Solution, RustleError>;
+
+    // A short human readable description, e.g. "a random solution".
+    fn describe(&self) -> String;
+}
+
+// The NYT daily puzzle for a date, served from `base_url` and cached on disk when a cache is given.
+pub struct NytSource {
+    base_url: String,
+    date: NaiveDate,
+    cache: Option<SolutionCache>
+}
+
+impl NytSource {
+    pub fn new(date: NaiveDate, cache: Option<SolutionCache>) -> NytSource {
+        NytSource::with_base_url(NYT_BASE_URL, date, cache)
+    }
+
+    pub fn with_base_url(base_url: &str, date: NaiveDate, cache: Option<SolutionCache>) -> NytSource {
+        NytSource { base_url: base_url.trim_end_matches('/').to_string(), date, cache }
+    }
+
+    // Fetches a daily puzzle straight from the network, bypassing the cache.
+    pub fn fetch(&self, date: NaiveDate) -> Result<SolutionResponse, RustleError> {
+        let nyt_wordlist_url = format!("{}/{}.json", self.base_url, date.format("%Y-%m-%d"));
+
+        let word_req = match reqwest::blocking::get(nyt_wordlist_url) {
+            Ok(res) => res,
+            Err(_) => return Err(RustleError::Network(String::from("Failed to retrieve the remote solution.")))
+        };
+
+        if word_req.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(RustleError::Network(format!("The NYT archive has no puzzle for {} (#{}).", date, puzzle_number(date))))
+        } else if !word_req.status().is_success() {
+            return Err(RustleError::Network(String::from("Failed to retrieve the remote solution.")))
+        }
+
+        let word_json: Result<SolutionResponse, reqwest::Error> = word_req.json();
+
+        match word_json {
+            Ok(res) => Ok(res),
+            Err(_) => Err(RustleError::Parse(String::from("Failed to parse remote soltuion JSON.")))
+        }
+    }
+
+    // Fetches and caches the daily puzzles for `days` days starting at `start`, reporting how each went.
+    pub fn prefetch(&self, start: NaiveDate, days: u64) -> Vec<(NaiveDate, Result<(), RustleError>)> {
+        start.iter_days().take(usize::try_from(days).unwrap_or(usize::MAX)).map(|date| {
+            let result = match &self.cache {
+                Some(cache) => self.fetch(date)
+                    .and_then(|response| cache.store(date, &response)),
+                None => Err(RustleError::Storage(String::from("No solution cache to prefetch into.")))
+            };
+
+            (date, result)
+        }).collect()
+    }
+}
+
+impl SolutionSource for NytSource {
+    // Looks the puzzle up in the cache before going to the network, caching anything fetched.
+    // Failing to write the cache never stops the puzzle from being played.
+    fn get_solution(&self, _answerlist: &[String]) -> Result<Solution, RustleError> {
+        let cached = self.cache.as_ref().and_then(|cache| cache.get(self.date));
+
+        let response = match cached {
+            Some(response) => response,
+            None => {
+                let response = self.fetch(self.date)?;
+
+                if let Some(cache) = &self.cache {
+                    let _ = cache.store(self.date, &response);
+                }
+
+                response
+            }
+        };
+
+        Ok(Solution {
+            word: response.solution.to_lowercase(),
+            puzzle_date: Some(self.date),
+            daily_details: Some(response),
+            seed: None,
+            fallback_errors: Vec::new()
+        })
+    }
+
+    fn describe(&self) -> String {
+        format!("the daily puzzle for {}", self.date)
+    }
+}
+
+// Picks the answer for a seed. This has to give the same word for the same seed and answer pool in
+// every release, so it uses its own SplitMix64 mix rather than whatever `rand` happens to provide.
+pub fn seeded_solution(seed: u64, answerlist: &[String]) -> Option<&String> {
+    let mut mixed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    mixed ^= mixed >> 31;
+
+    let index = usize::try_from(mixed % u64::try_from(answerlist.len()).ok()?.max(1)).ok()?;
+
+    answerlist.get(index)
+}
+
+// A word from the answer pool picked by a seed. Without a seed one is generated at random, and
+// either way it's kept on the solution so the same game can be played again.
+pub struct RandomSource {
+    seed: Option<u64>
+}
+
+impl RandomSource {
+    pub fn new(seed: Option<u64>) -> RandomSource {
+        RandomSource { seed }
+    }
+}
+
+impl SolutionSource for RandomSource {
+    fn get_solution(&self, answerlist: &[String]) -> Result<Solution, RustleError> {
+        // Keep generated seeds short enough to read out to a friend.
+        let seed = self.seed.unwrap_or_else(|| rand::thread_rng().gen_range(0..100_000));
+
+        match seeded_solution(seed, answerlist) {
+            Some(rand_solution) => Ok(Solution { seed: Some(seed), ..Solution::offline(rand_solution.to_owned()) }),
+            None => Err(RustleError::Wordlist(String::from("Failed to retrieve a new local word")))
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self.seed {
+            Some(seed) => format!("the solution for seed {}", seed),
+            None => String::from("a random solution")
+        }
+    }
+}
+
+// Always the same word.
+pub struct FixedSource {
+    word: String
+}
+
+impl FixedSource {
+    pub fn new(word: &str) -> FixedSource {
+        FixedSource { word: word.trim().to_lowercase() }
+    }
+}
+
+impl SolutionSource for FixedSource {
+    fn get_solution(&self, _answerlist: &[String]) -> Result<Solution, RustleError> {
+        Ok(Solution::offline(self.word.clone()))
+    }
+
+    fn describe(&self) -> String {
+        String::from("a fixed solution")
+    }
+}
+
+// A word read from a file, either a saved NYT JSON response or plain text with the word on the
+// first non-empty line.
+pub struct FileSource {
+    path: PathBuf
+}
+
+impl FileSource {
+    pub fn new(path: PathBuf) -> FileSource {
+        FileSource { path }
+    }
+}
+
+impl SolutionSource for FileSource {
+    fn get_solution(&self, _answerlist: &[String]) -> Result<Solution, RustleError> {
+        let contents = fs::read_to_string(&self.path)
+            .map_err(|err| RustleError::Storage(format!("Failed to read solution file \"{}\": {}", self.path.display(), err)))?;
+
+        if let Ok(response) = serde_json::from_str::<SolutionResponse>(&contents) {
+            return Ok(Solution::offline(response.solution.to_lowercase()))
+        }
+
+        match contents.lines().map(str::trim).find(|line| !line.is_empty()) {
+            Some(word) => Ok(Solution::offline(word.to_lowercase())),
+            None => Err(RustleError::Parse(format!("Solution file \"{}\" is empty.", self.path.display())))
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!("the solution from \"{}\"", self.path.display())
+    }
+}
+
+// Tries each source in turn, using the first that succeeds. The errors from any sources that were
+// skipped are kept on the solution so they can be reported.
+pub struct FallbackChain {
+    sources: Vec<Box<dyn SolutionSource>>
+}
+
+impl FallbackChain {
+    pub fn new(sources: Vec<Box<dyn SolutionSource>>) -> FallbackChain {
+        FallbackChain { sources }
+    }
+}
+
+impl SolutionSource for FallbackChain {
+    fn get_solution(&self, answerlist: &[String]) -> Result<Solution, RustleError> {
+        let mut fallback_errors: Vec<String> = Vec::new();
+
+        for (index, source) in self.sources.iter().enumerate() {
+            match source.get_solution(answerlist) {
+                Ok(mut solution) => {
+                    fallback_errors.append(&mut solution.fallback_errors);
+                    solution.fallback_errors = fallback_errors;
+                    return Ok(solution)
+                },
+                Err(err) => match self.sources.get(index + 1) {
+                    Some(next_source) => fallback_errors.push(format!("{} Using {} instead.", err, next_source.describe())),
+                    None => return Err(err)
+                }
+            }
+        }
+
+        Err(RustleError::Wordlist(String::from("No solution sources were given.")))
+    }
+
+    fn describe(&self) -> String {
+        self.sources.first().map_or(String::from("nothing"), |source| source.describe())
+    }
+}
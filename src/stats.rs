@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::RustleError;
+use crate::storage;
+
+// The layout of the stats file. There's only been one so far, and a file from a newer Rustle is
+// rejected by Stats::load rather than overwritten with fewer fields.
+pub const STATS_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct Stats {
+    version: u32,
+    games_played: u32,
+    games_won: u32,
+    current_streak: u32,
+    max_streak: u32,
+    guess_distribution: Vec<u32> // Index 0 counts wins in one guess, index 1 wins in two, etc.
+}
+
+impl Default for Stats {
+    fn default() -> Stats {
+        Stats {
+            version: STATS_VERSION,
+            games_played: 0,
+            games_won: 0,
+            current_streak: 0,
+            max_streak: 0,
+            guess_distribution: vec![0; 6]
+        }
+    }
+}
+
+impl Stats {
+    // Stats live under the XDG data directory (e.g. ~/.local/share/rustle/stats.json).
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("rustle").join("stats.json"))
+    }
+
+    pub fn load(path: &Path) -> Result<Stats, RustleError> {
+        if !path.exists() {
+            return Ok(Stats::default())
+        }
+
+        let raw_stats = fs::read_to_string(path)
+            .map_err(|err| RustleError::Storage(format!("Failed to read stats file \"{}\": {}", path.display(), err)))?;
+        let mut stats: Stats = serde_json::from_str(&raw_stats)
+            .map_err(|err| RustleError::Parse(format!("Stats file \"{}\" is corrupt: {}", path.display(), err)))?;
+
+        if stats.version > STATS_VERSION {
+            return Err(RustleError::Storage(format!(
+                "Stats file \"{}\" is version {}, but this Rustle only understands up to version {}.",
+                path.display(),
+                stats.version,
+                STATS_VERSION
+            )))
+        }
+
+        stats.version = STATS_VERSION;
+
+        Ok(stats)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), RustleError> {
+        storage::write_json(path, self, |err| RustleError::Storage(format!("Failed to write stats file \"{}\": {}", path.display(), err)))
+    }
+
+    pub fn record_win(&mut self, guesses: u8) {
+        let index = usize::from(guesses.max(1)) - 1;
+
+        if self.guess_distribution.len() <= index {
+            self.guess_distribution.resize(index + 1, 0);
+        }
+
+        self.guess_distribution[index] += 1;
+        self.games_played += 1;
+        self.games_won += 1;
+        self.current_streak += 1;
+        self.max_streak = self.max_streak.max(self.current_streak);
+    }
+
+    pub fn record_loss(&mut self) {
+        self.games_played += 1;
+        self.current_streak = 0;
+    }
+
+    pub fn games_played(&self) -> u32 {
+        self.games_played
+    }
+
+    pub fn games_won(&self) -> u32 {
+        self.games_won
+    }
+
+    pub fn win_percentage(&self) -> u32 {
+        if self.games_played == 0 {
+            return 0
+        }
+
+        ((f64::from(self.games_won) * 100.0) / f64::from(self.games_played)).round() as u32
+    }
+
+    pub fn current_streak(&self) -> u32 {
+        self.current_streak
+    }
+
+    pub fn max_streak(&self) -> u32 {
+        self.max_streak
+    }
+
+    pub fn guess_distribution(&self) -> &Vec<u32> {
+        &self.guess_distribution
+    }
+}
@@ -0,0 +1,27 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::error::RustleError;
+
+// Writes `value` to `path` as JSON, creating its directory if needed. It's written to a sibling file
+// first and renamed over `path`, so a crash mid-write can't leave a half written file behind.
+// `storage_err` turns a failed write into the caller's own error message.
+pub(crate) fn write_json<T, E>(path: &Path, value: &T, storage_err: E) -> Result<(), RustleError>
+    where
+        T: Serialize,
+        E: Fn(io::Error) -> RustleError
+    {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(&storage_err)?;
+    }
+
+    let tmp_path = path.with_extension("json.tmp");
+    let raw_json = serde_json::to_string_pretty(value).map_err(|err| RustleError::Parse(err.to_string()))?;
+    fs::write(&tmp_path, raw_json).map_err(&storage_err)?;
+    fs::rename(&tmp_path, path).map_err(&storage_err)?;
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use std::str::FromStr;
+
+use crate::display::{self, TermFormatter};
+use crate::error::RustleError;
+use crate::LetterState;
+
+// How tiles, keys and the stats bars are drawn. The colored themes differ only in their palette,
+// while monochrome marks each state with symbols so it works without any color at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    #[default]
+    Default, // Bright green and yellow with black letters, for dark terminals.
+    HighContrast, // Orange and blue, which colorblind players can tell apart.
+    Light, // Darker tiles with white letters, for light terminals.
+    Monochrome // [A] for right, (A) for misplaced and ·A· for wrong, no color.
+}
+
+// How many colors the terminal can show, from its TERM and COLORTERM variables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    Basic, // The 16 standard colors, which every terminal has.
+    Ansi256,
+    TrueColor
+}
+
+// A color at every depth: its index among the 16 standard colors, among the 256 indexed ones, and
+// its exact RGB value.
+#[derive(Debug, Clone, Copy)]
+struct Color {
+    basic: u8,
+    indexed: u8,
+    rgb: (u8, u8, u8)
+}
+
+const BLACK: Color = Color { basic: 0, indexed: 16, rgb: (0, 0, 0) };
+const WHITE: Color = Color { basic: 15, indexed: 231, rgb: (255, 255, 255) };
+
+// The (background, letter) colors for each state of a colored theme.
+struct Palette {
+    correct: (Color, Color),
+    exists: (Color, Color),
+    incorrect: (Color, Color),
+    empty: (Color, Color) // Unplayed tiles and unused keys.
+}
+
+const DEFAULT_PALETTE: Palette = Palette {
+    correct: (Color { basic: 10, indexed: 71, rgb: (106, 170, 100) }, BLACK),
+    exists: (Color { basic: 11, indexed: 179, rgb: (201, 180, 88) }, BLACK),
+    incorrect: (Color { basic: 8, indexed: 244, rgb: (120, 124, 126) }, BLACK),
+    empty: (Color { basic: 7, indexed: 252, rgb: (211, 214, 218) }, BLACK)
+};
+
+const HIGH_CONTRAST_PALETTE: Palette = Palette {
+    correct: (Color { basic: 9, indexed: 209, rgb: (245, 121, 58) }, BLACK),
+    exists: (Color { basic: 12, indexed: 111, rgb: (133, 192, 249) }, BLACK),
+    incorrect: (Color { basic: 8, indexed: 244, rgb: (120, 124, 126) }, BLACK),
+    empty: (Color { basic: 7, indexed: 252, rgb: (211, 214, 218) }, BLACK)
+};
+
+const LIGHT_PALETTE: Palette = Palette {
+    correct: (Color { basic: 2, indexed: 65, rgb: (83, 141, 78) }, WHITE),
+    exists: (Color { basic: 3, indexed: 136, rgb: (181, 159, 59) }, WHITE),
+    incorrect: (Color { basic: 8, indexed: 243, rgb: (120, 124, 126) }, WHITE),
+    empty: (Color { basic: 7, indexed: 252, rgb: (211, 214, 218) }, BLACK)
+};
+
+impl Theme {
+    pub const ALL: [Theme; 4] = [Theme::Default, Theme::HighContrast, Theme::Light, Theme::Monochrome];
+
+    // The name given to --theme.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Default => "default",
+            Theme::HighContrast => "contrast",
+            Theme::Light => "light",
+            Theme::Monochrome => "mono"
+        }
+    }
+
+    fn palette(&self) -> Option<&'static Palette> {
+        match self {
+            Theme::Default => Some(&DEFAULT_PALETTE),
+            Theme::HighContrast => Some(&HIGH_CONTRAST_PALETTE),
+            Theme::Light => Some(&LIGHT_PALETTE),
+            Theme::Monochrome => None
+        }
+    }
+
+    // A tile (or key) three characters wide showing `letter` in the given state, e.g. " A " on a
+    // green background, or "[A]" in monochrome.
+    pub fn tile(&self, letter: char, status: &LetterState, depth: ColorDepth) -> String {
+        let Some(palette) = self.palette() else {
+            return match status {
+                LetterState::Correct => format!("[{}]", letter),
+                LetterState::Exists => format!("({})", letter),
+                LetterState::Incorrect => format!("·{}·", letter),
+                LetterState::NotExists => format!(" {} ", letter)
+            }
+        };
+
+        let (background, foreground) = match status {
+            LetterState::Correct => palette.correct,
+            LetterState::Exists => palette.exists,
+            LetterState::Incorrect => palette.incorrect,
+            LetterState::NotExists => palette.empty
+        };
+
+        format!(
+            "{}{} {} {}",
+            display::get_bold(&foreground.escape(depth, false)),
+            background.escape(depth, true),
+            letter,
+            TermFormatter::Clear.as_str()
+        )
+    }
+
+    // A stats bar `width` characters wide, in the correct color for the game that was just won.
+    pub fn bar(&self, width: usize, highlight: bool, depth: ColorDepth) -> String {
+        let Some(palette) = self.palette() else {
+            return if highlight { "#".repeat(width) } else { "=".repeat(width) }
+        };
+
+        let color = if highlight { palette.correct.0 } else { palette.incorrect.0 };
+
+        format!("{}{}{}", color.escape(depth, true), " ".repeat(width), TermFormatter::Clear.as_str())
+    }
+}
+
+impl FromStr for Theme {
+    type Err = RustleError;
+
+    fn from_str(name: &str) -> Result<Theme, RustleError> {
+        match name.to_lowercase().as_str() {
+            "default" => Ok(Theme::Default),
+            "contrast" | "high-contrast" => Ok(Theme::HighContrast),
+            "light" => Ok(Theme::Light),
+            "mono" | "monochrome" => Ok(Theme::Monochrome),
+            _ => Err(RustleError::Parse(format!(
+                "Unknown theme \"{}\", expected one of: {}",
+                name,
+                Theme::ALL.map(|theme| theme.name()).join(", ")
+            )))
+        }
+    }
+}
+
+impl Color {
+    // The escape code that sets this as the background (or letter) color.
+    fn escape(&self, depth: ColorDepth, background: bool) -> String {
+        match depth {
+            ColorDepth::Basic => {
+                // The standard colors are 30-37 (40-47 for backgrounds), and their bright versions 90-97 (100-107).
+                let base = if self.basic < 8 { 30 } else { 90 - 8 };
+                format!("\x1b[{}m", base + u16::from(self.basic) + if background { 10 } else { 0 })
+            },
+            ColorDepth::Ansi256 => format!("\x1b[{};5;{}m", if background { 48 } else { 38 }, self.indexed),
+            ColorDepth::TrueColor => format!("\x1b[{};2;{};{};{}m", if background { 48 } else { 38 }, self.rgb.0, self.rgb.1, self.rgb.2)
+        }
+    }
+}
+
+impl ColorDepth {
+    // The depth the terminal advertises: COLORTERM=truecolor (or 24bit) for RGB colors, and a TERM
+    // ending in 256color (e.g. xterm-256color) for the 256 indexed ones.
+    pub fn detect() -> ColorDepth {
+        ColorDepth::from_env(std::env::var("COLORTERM").ok().as_deref(), std::env::var("TERM").ok().as_deref())
+    }
+
+    pub fn from_env(colorterm: Option<&str>, term: Option<&str>) -> ColorDepth {
+        match (colorterm, term) {
+            (Some("truecolor" | "24bit"), _) => ColorDepth::TrueColor,
+            (_, Some(term)) if term.contains("256color") => ColorDepth::Ansi256,
+            _ => ColorDepth::Basic
+        }
+    }
+}
@@ -1,13 +1,25 @@
-use std::error::Error;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
 
-use chrono::Datelike;
-use serde::Deserialize;
-use rand::seq::SliceRandom;
-use crate::display::TermFormatter;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use crate::error::RustleError;
+use crate::source::SolutionSource;
+use crate::lang::Language;
 
-#[derive(Deserialize)]
-struct SolutionResponse {
-    solution: String
+// A daily puzzle as served by the NYT, e.g. https://www.nytimes.com/svc/wordle/v2/2024-03-15.json
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SolutionResponse {
+    #[serde(default)]
+    pub id: u64,
+    pub solution: String,
+    #[serde(default)]
+    pub print_date: String,
+    #[serde(default)]
+    pub days_since_launch: i64,
+    #[serde(default)]
+    pub editor: Option<String>
 }
 
 #[derive(Deserialize)]
@@ -15,96 +27,291 @@ struct WordList {
     wordlist: Vec<String>
 }
 
-pub struct WordleWords {
-    solution: String,
-    wordlist: Vec<String>,
-    offline: bool
+// The first daily Wordle (#0) was published on 2021-06-19.
+pub fn first_puzzle_date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2021, 6, 19).expect("Invalid first puzzle date")
 }
 
-impl WordleWords {
-    pub fn new(offline: bool) -> Result<WordleWords, Box<dyn Error>> {
-        let mut offline = offline;
-        let wordlist = WordleWords::load_wordlist()?;
-
-        let solution: String;
-
-        if !offline {
-            let remote_solution = WordleWords::get_remote_solution();
-
-            solution = match remote_solution {
-                Ok(sol) => sol,
-                Err(err) => {
-                    println!(
-                        "{}{} A random solution will be used.{}",
-                        TermFormatter::RedBold.as_str(),
-                        err,
-                        TermFormatter::Clear.as_str()
-                    );
-                    offline = true;
-                    WordleWords::get_random_local_solution(&wordlist)?
-                }
-            }
+// The daily puzzle number for a given date, as shown in the share text (e.g. "Wordle 1,000").
+pub fn puzzle_number(date: NaiveDate) -> i64 {
+    date.signed_duration_since(first_puzzle_date()).num_days()
+}
+
+// The date a given daily puzzle number was (or will be) published on.
+pub fn puzzle_date(number: i64) -> Result<NaiveDate, RustleError> {
+    if number < 0 {
+        return Err(RustleError::Parse(format!("Puzzle #{} doesn't exist, the first daily puzzle is #0.", number)))
+    }
+
+    first_puzzle_date().checked_add_days(chrono::Days::new(number.unsigned_abs()))
+        .ok_or_else(|| RustleError::Parse(format!("Puzzle #{} is too far in the future.", number)))
+}
+
+// Parses a YYYY-MM-DD date for a daily puzzle, rejecting dates before the first puzzle.
+pub fn parse_puzzle_date(date: &str) -> Result<NaiveDate, RustleError> {
+    let parsed = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| RustleError::Parse(format!("Invalid date \"{}\", expected the format YYYY-MM-DD.", date)))?;
+
+    if parsed < first_puzzle_date() {
+        return Err(RustleError::Parse(format!("No daily puzzle exists for {}, the first was published on {}.", parsed, first_puzzle_date())))
+    }
+
+    Ok(parsed)
+}
+
+pub const MIN_WORD_LENGTH: usize = 4;
+pub const MAX_WORD_LENGTH: usize = 8;
+pub const DEFAULT_WORD_LENGTH: usize = 5;
+
+// The bundled (guess list, answer list) JSON for a language and word length. English five letter
+// words use the NYT lists and the other lengths are built from the SCOWL american-english dictionary.
+// The other word packs only have five letter words, see the README for where they come from.
+fn bundled_wordlists(language: Language, word_length: usize) -> Option<(&'static str, &'static str)> {
+    match (language, word_length) {
+        (Language::Spanish, 5) => Some((include_str!("assets/lang/es/wordlist.json"), include_str!("assets/lang/es/answers.json"))),
+        (Language::German, 5) => Some((include_str!("assets/lang/de/wordlist.json"), include_str!("assets/lang/de/answers.json"))),
+        (Language::French, 5) => Some((include_str!("assets/lang/fr/wordlist.json"), include_str!("assets/lang/fr/answers.json"))),
+        (Language::English, _) => english_wordlists(word_length),
+        _ => None
+    }
+}
+
+fn english_wordlists(word_length: usize) -> Option<(&'static str, &'static str)> {
+    match word_length {
+        4 => Some((include_str!("assets/wordlist_4.json"), include_str!("assets/answers_4.json"))),
+        5 => Some((include_str!("assets/wordlist.json"), include_str!("assets/answers.json"))),
+        6 => Some((include_str!("assets/wordlist_6.json"), include_str!("assets/answers_6.json"))),
+        7 => Some((include_str!("assets/wordlist_7.json"), include_str!("assets/answers_7.json"))),
+        8 => Some((include_str!("assets/wordlist_8.json"), include_str!("assets/answers_8.json"))),
+        _ => None
+    }
+}
+
+// The bundled (guess list, answer list) for a language and word length.
+pub fn load_bundled_words(language: Language, word_length: usize) -> Result<(Vec<String>, Vec<String>), RustleError> {
+    let Some((raw_wordlist, raw_answerlist)) = bundled_wordlists(language, word_length) else {
+        return Err(RustleError::Wordlist(match language {
+            Language::English => format!("Words must be between {} and {} letters long.", MIN_WORD_LENGTH, MAX_WORD_LENGTH),
+            _ => format!("The {} word pack only has five letter words.", language.name())
+        }))
+    };
+
+    Ok((load_wordlist(raw_wordlist)?, load_wordlist(raw_answerlist)?))
+}
+
+fn load_wordlist(raw_wordlist: &str) -> Result<Vec<String>, RustleError> {
+    let wordlist: WordList = serde_json::from_str(raw_wordlist)
+        .map_err(|err| RustleError::Wordlist(format!("Failed to load the bundled wordlist: {}", err)))?;
+
+    Ok(wordlist.wordlist)
+}
+
+// Loads a word list from disk for --wordlist or --answers, see parse_wordlist.
+pub fn load_wordlist_file(path: &Path, language: Language, word_length: usize) -> Result<Vec<String>, RustleError> {
+    let raw_wordlist = fs::read_to_string(path)
+        .map_err(|err| RustleError::Wordlist(format!("Failed to read the word list \"{}\": {}", path.display(), err)))?;
+
+    parse_wordlist(&raw_wordlist, path, language, word_length)
+}
+
+// Parses a user supplied word list, either in the same {"wordlist": [...]} JSON as the bundled lists
+// or as plain text with one word per line, where blank lines and lines starting with # are skipped.
+// Words are lowercased, and every duplicate, wrong length or non-alphabetic entry is reported with
+// its line number so a list can be fixed in one go. `path` is only used to name the list in errors.
+pub fn parse_wordlist(raw_wordlist: &str, path: &Path, language: Language, word_length: usize) -> Result<Vec<String>, RustleError> {
+    const MAX_REPORTED_PROBLEMS: usize = 20;
+
+    let entries = if raw_wordlist.trim_start().starts_with('{') {
+        json_wordlist_entries(raw_wordlist)
+            .map_err(|err| RustleError::Wordlist(format!("The word list \"{}\" isn't a valid word list: {}", path.display(), err)))?
+    } else {
+        raw_wordlist.lines().enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(line_number, line)| (line_number, line.to_string()))
+            .collect()
+    };
+
+    let mut words: Vec<String> = Vec::new();
+    let mut first_seen: HashMap<String, usize> = HashMap::new();
+    let mut problems: Vec<String> = Vec::new();
+
+    for (line_number, entry) in entries {
+        let word = entry.to_lowercase();
+        let length = word.chars().count();
+
+        if length != word_length {
+            problems.push(format!("line {}: \"{}\" is {} letters long, expected {}", line_number, entry, length, word_length));
+        } else if !word.chars().all(|letter| language.is_letter(letter)) {
+            problems.push(format!("line {}: \"{}\" has characters that aren't {} letters", line_number, entry, language.name()));
+        } else if let Some(first_line) = first_seen.get(&word) {
+            problems.push(format!("line {}: \"{}\" is a duplicate of line {}", line_number, entry, first_line));
         } else {
-            solution = WordleWords::get_random_local_solution(&wordlist)?;
+            first_seen.insert(word.clone(), line_number);
+            words.push(word);
+        }
+    }
+
+    if !problems.is_empty() {
+        let mut report = format!("The word list \"{}\" has {} problem{}:", path.display(), problems.len(), if problems.len() == 1 { "" } else { "s" });
+
+        for problem in problems.iter().take(MAX_REPORTED_PROBLEMS) {
+            report.push_str(&format!("\n  {}", problem));
+        }
+        if problems.len() > MAX_REPORTED_PROBLEMS {
+            report.push_str(&format!("\n  ...and {} more", problems.len() - MAX_REPORTED_PROBLEMS));
         }
 
-        Ok(WordleWords { solution: solution, wordlist: wordlist, offline: offline})
+        return Err(RustleError::Wordlist(report))
     }
 
-    fn get_remote_solution() -> Result<String, &'static str> {
-        let day: String = if chrono::Local::now().date_naive().day() < 10 {
-            format!("0{}", chrono::Local::now().date_naive().day())
-        } else {
-            chrono::Local::now().date_naive().day().to_string()
-        };
+    if words.is_empty() {
+        return Err(RustleError::Wordlist(format!("The word list \"{}\" doesn't have any words in it.", path.display())))
+    }
 
-        let month: String = if chrono::Local::now().date_naive().month() < 10 {
-            format!("0{}", chrono::Local::now().date_naive().month())
-        } else {
-            chrono::Local::now().date_naive().month().to_string()
-        };
+    Ok(words)
+}
+
+// The entries of a JSON word list paired with the line each one is on. serde_json doesn't keep
+// positions, so each entry is found by searching onwards from the one before it. serde_json's errors
+// already say which line and column they're on.
+fn json_wordlist_entries(raw_wordlist: &str) -> Result<Vec<(usize, String)>, serde_json::Error> {
+    let wordlist: WordList = serde_json::from_str(raw_wordlist)?;
+
+    let mut search_from = raw_wordlist.find("\"wordlist\"").unwrap_or(0);
 
-        let date_str = format!("{}-{}-{}", chrono::Local::now().date_naive().year(), month, day);
-        let nyt_wordlist_url = format!("https://www.nytimes.com/svc/wordle/v2/{}.json", date_str);
+    Ok(wordlist.wordlist.into_iter().map(|entry| {
+        let quoted = serde_json::to_string(&entry).unwrap_or_default();
 
-        let word_req = reqwest::blocking::get(nyt_wordlist_url);
-        
-        let word_json: Result<SolutionResponse, reqwest::Error> = match word_req {
-            Ok(json_res) => json_res.json(),
-            Err(_) => return Err("Failed to retrieve the remote solution.")
+        // Entries written with escapes won't be found, and get the line of the entry before them.
+        let offset = match raw_wordlist[search_from..].find(&quoted) {
+            Some(found) => {
+                search_from += found + quoted.len();
+                search_from - quoted.len()
+            },
+            None => search_from
         };
 
-        match word_json {
-            Ok(res) => return Ok(res.solution),
-            Err(_) => return Err("Failed to parse remote soltuion JSON.")
-        }
+        (raw_wordlist[..offset].matches('\n').count() + 1, entry)
+    }).collect())
+}
+
+pub struct WordleWords {
+    solution: String,
+    word_length: usize,
+    language: Language,
+    puzzle_date: Option<NaiveDate>, // The date of the daily puzzle being played, None for random solutions.
+    daily_details: Option<SolutionResponse>, // Everything the NYT told us about the daily puzzle.
+    seed: Option<u64>, // The seed a random solution was picked with.
+    fallback_errors: Vec<String>, // Why any earlier sources in a FallbackChain were passed over.
+    answerlist: Vec<String>, // Curated pool that solutions are drawn from.
+    wordlist: Vec<String>, // Every word accepted as a guess.
+    custom_lists: bool, // Whether the lists were given with --wordlist or --answers rather than bundled.
+    offline: bool
+}
+
+impl WordleWords {
+    // Loads the English wordlists for a word length and picks a solution from the given source. If any sources in a
+    // fallback chain failed along the way, the reasons are kept in get_fallback_errors.
+    pub fn new(source: &dyn SolutionSource, word_length: usize) -> Result<WordleWords, RustleError> {
+        WordleWords::with_language(source, Language::English, word_length)
     }
 
-    fn get_random_local_solution(wordlist: &Vec<String>) -> Result<String, &str> {
-        match wordlist.choose(&mut rand::thread_rng()) {
-            Some(rand_solution) => Ok(rand_solution.to_owned()),
-            None => Err("Failed to retrieve a new local word")
-        }
+    // Like new, but with the word pack for another language.
+    pub fn with_language(source: &dyn SolutionSource, language: Language, word_length: usize) -> Result<WordleWords, RustleError> {
+        let (wordlist, answerlist) = load_bundled_words(language, word_length)?;
+
+        WordleWords::from_lists(source, language, word_length, wordlist, answerlist, false)
     }
 
-    fn load_wordlist() -> Result<Vec<String>, Box<dyn Error>> {
-        let raw_wordlist = include_str!("assets/wordlist.json");
+    // Like with_language, but with word lists loaded from elsewhere, e.g. with load_wordlist_file.
+    pub fn with_lists(source: &dyn SolutionSource, language: Language, word_length: usize, wordlist: Vec<String>, answerlist: Vec<String>) -> Result<WordleWords, RustleError> {
+        WordleWords::from_lists(source, language, word_length, wordlist, answerlist, true)
+    }
+
+    fn from_lists(source: &dyn SolutionSource, language: Language, word_length: usize, mut wordlist: Vec<String>, mut answerlist: Vec<String>, custom_lists: bool) -> Result<WordleWords, RustleError> {
+        let solution = source.get_solution(&answerlist)?;
 
-        let wordlist: WordList = serde_json::from_str(raw_wordlist)?;
+        if solution.word.chars().count() != word_length {
+            return Err(RustleError::Wordlist(format!("The solution \"{}\" isn't {} letters long.", solution.word, word_length)))
+        }
 
-        Ok(wordlist.wordlist)
+        // The solution can always be guessed, even when it comes from somewhere other than the answer
+        // list (a new NYT word, or a fixed or file source). The solver has to know it's a possible
+        // answer, and only ranks words on the guess list, so it goes on both.
+        if !answerlist.contains(&solution.word) {
+            answerlist.push(solution.word.clone());
+        }
+        if !wordlist.contains(&solution.word) {
+            wordlist.push(solution.word.clone());
+        }
+
+        Ok(WordleWords {
+            offline: solution.daily_details.is_none(),
+            puzzle_date: solution.puzzle_date,
+            daily_details: solution.daily_details,
+            seed: solution.seed,
+            fallback_errors: solution.fallback_errors,
+            solution: solution.word,
+            word_length,
+            language,
+            answerlist,
+            wordlist,
+            custom_lists
+        })
     }
 
     pub fn is_offline(&self) -> bool {
-        return self.offline
+        self.offline
+    }
+
+    pub fn get_puzzle_date(&self) -> Option<NaiveDate> {
+        self.puzzle_date
+    }
+
+    pub fn get_daily_details(&self) -> Option<&SolutionResponse> {
+        self.daily_details.as_ref()
+    }
+
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn get_fallback_errors(&self) -> &[String] {
+        &self.fallback_errors
+    }
+
+    pub fn get_puzzle_number(&self) -> Option<i64> {
+        self.puzzle_date.map(puzzle_number)
+    }
+
+    pub fn get_word_length(&self) -> usize {
+        self.word_length
+    }
+
+    pub fn get_language(&self) -> Language {
+        self.language
     }
 
     pub fn get_solution(&self) -> &String {
-        return &self.solution
+        &self.solution
     }
 
     pub fn get_wordlist(&self) -> &Vec<String> {
-        return &self.wordlist
+        &self.wordlist
+    }
+
+    pub fn get_answerlist(&self) -> &Vec<String> {
+        &self.answerlist
+    }
+
+    pub fn has_custom_lists(&self) -> bool {
+        self.custom_lists
+    }
+
+    pub fn is_valid_guess(&self, guess: &str) -> bool {
+        let guess = guess.to_lowercase();
+        self.wordlist.contains(&guess) || self.answerlist.contains(&guess)
     }
 
 }
\ No newline at end of file
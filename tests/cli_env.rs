@@ -0,0 +1,77 @@
+// Checks that options can come from RUSTLE_ environment variables. Changing the environment while
+// other threads read it isn't safe, so this lives in its own test binary with a single test, and
+// the variables are always put back, even when an assertion fails.
+
+use std::env;
+use std::ffi::OsString;
+
+use rustle::cli::{self, Command, PlayArgs};
+use rustle::config::Config;
+use rustle::lang::Language;
+
+// Sets environment variables for as long as it's alive, then restores their old values.
+struct EnvGuard {
+    saved: Vec<(&'static str, Option<OsString>)>
+}
+
+impl EnvGuard {
+    fn set(variables: &[(&'static str, &str)]) -> EnvGuard {
+        let saved = variables.iter().map(|(name, value)| {
+            let old = env::var_os(name);
+            env::set_var(name, value);
+            (*name, old)
+        }).collect();
+
+        EnvGuard { saved }
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        for (name, old) in &self.saved {
+            match old {
+                Some(value) => env::set_var(name, value),
+                None => env::remove_var(name)
+            }
+        }
+    }
+}
+
+fn parse_play(args: &[&str], config: &Config) -> PlayArgs {
+    match cli::parse_args_with_config(std::iter::once("rustle").chain(args.iter().copied()), config).map(|cli| cli.command) {
+        Ok(Command::Play(play)) => play,
+        other => panic!("{:?} didn't parse as a game: {:?}", args, other)
+    }
+}
+
+#[test]
+fn options_come_from_the_environment() {
+    let no_config = Config::default();
+
+    {
+        let _env = EnvGuard::set(&[("RUSTLE_TRIES", "9"), ("RUSTLE_HARD", "1"), ("RUSTLE_LANG", "fr")]);
+
+        let from_env = parse_play(&[], &no_config);
+        assert_eq!((from_env.tries, from_env.hard, from_env.words.lang), (9, true, Language::French));
+
+        // The command line wins over the environment.
+        assert_eq!(parse_play(&["--tries", "4"], &no_config).tries, 4);
+
+        // And the environment wins over the config file.
+        let config = Config { tries: Some(7), hard: Some(false), length: Some(6), ..Config::default() };
+        let configured = parse_play(&[], &config);
+        assert_eq!((configured.tries, configured.hard, configured.words.length), (9, true, 6));
+    }
+
+    {
+        let _env = EnvGuard::set(&[("RUSTLE_HARD", "off")]);
+        assert!(!parse_play(&[], &no_config).hard);
+    }
+
+    {
+        // A bad value only matters to the commands that have the option.
+        let _env = EnvGuard::set(&[("RUSTLE_TRIES", "99")]);
+        assert!(cli::parse_args(["rustle"]).is_err());
+        assert!(cli::parse_args(["rustle", "stats"]).is_ok());
+    }
+}